@@ -27,20 +27,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             break;
         }
 
-        let mut request = GenerationRequest::new("llama2:latest".into(), input.to_string());
+        let mut request = GenerationRequest::new("llama2:latest", input.to_string());
         if let Some(context) = context.clone() {
             request = request.context(context);
         }
         let mut stream: GenerationResponseStream = ollama.generate_stream(request).await?;
 
-        while let Some(Ok(res)) = stream.next().await {
-            for ele in res {
-                stdout.write_all(ele.response.as_bytes()).await?;
-                stdout.flush().await?;
+        while let Some(res) = stream.next().await {
+            let res = res?;
+            stdout.write_all(res.response.as_bytes()).await?;
+            stdout.flush().await?;
 
-                if ele.context.is_some() {
-                    context = ele.context;
-                }
+            if res.context.is_some() {
+                context = res.context;
             }
         }
     }
@@ -0,0 +1,335 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use url::Url;
+
+use crate::{
+    error::OllamaError, generation::parameters::KeepAlive, models::ModelOptions,
+    retry::RetryPolicy, IntoUrl, Ollama,
+};
+
+/// The port the Ollama server listens on by default.
+const DEFAULT_PORT: u16 = 11434;
+
+/// A builder to configure an [`Ollama`] client.
+///
+/// Unlike [`Ollama::new`], building never panics: an invalid host or port is reported as an error.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use ollama_rs::{models::ModelOptions, Ollama};
+///
+/// let ollama = Ollama::builder()
+///     .host("https://example.com")
+///     .base_path("/ollama/")
+///     .connect_timeout(Duration::from_secs(5))
+///     .default_options(ModelOptions::default().temperature(0.2))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(ollama.url_str(), "https://example.com/ollama/");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OllamaBuilder {
+    host: Option<String>,
+    port: Option<u16>,
+    base_path: Option<String>,
+    connect_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    stream_idle_timeout: Option<Duration>,
+    default_options: Option<ModelOptions>,
+    default_keep_alive: Option<KeepAlive>,
+    default_headers: HeaderMap,
+    user_agent: Option<String>,
+    bearer_token: Option<String>,
+    proxy: Option<reqwest::Proxy>,
+    retry_policy: RetryPolicy,
+    check_capabilities: bool,
+    validate_model_capabilities: bool,
+}
+
+impl OllamaBuilder {
+    /// Creates a new builder. Without further configuration it builds the same client as [`Ollama::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The URL of the Ollama service, including the scheme. (Default: `http://127.0.0.1:11434`)
+    pub fn host(mut self, host: impl IntoUrl) -> Self {
+        self.host = Some(host.as_str().to_string());
+        self
+    }
+
+    /// The port of the Ollama service, overriding the one of the host.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// A path prefix for every endpoint, for servers behind a reverse proxy (e.g. `/ollama/`).
+    pub fn base_path(mut self, base_path: impl Into<String>) -> Self {
+        self.base_path = Some(base_path.into());
+        self
+    }
+
+    /// Timeout for establishing the connection to the server.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Timeout for requests whose response is not streamed, from sending the request until the whole response is read.
+    /// Blob uploads are not bounded by it.
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    /// Maximum time to wait for the next chunk of a streamed response.
+    pub fn stream_idle_timeout(mut self, stream_idle_timeout: Duration) -> Self {
+        self.stream_idle_timeout = Some(stream_idle_timeout);
+        self
+    }
+
+    /// Model options used by chat, generation and embeddings requests that do not set any.
+    pub fn default_options(mut self, default_options: ModelOptions) -> Self {
+        self.default_options = Some(default_options);
+        self
+    }
+
+    /// Keep alive used by requests that do not set any.
+    pub fn default_keep_alive(mut self, default_keep_alive: KeepAlive) -> Self {
+        self.default_keep_alive = Some(default_keep_alive);
+        self
+    }
+
+    /// Headers sent with every request.
+    pub fn default_headers(mut self, default_headers: HeaderMap) -> Self {
+        self.default_headers = default_headers;
+        self
+    }
+
+    /// The `User-Agent` header sent with every request.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// A token sent as `Authorization: Bearer <token>` with every request.
+    pub fn bearer_token(mut self, bearer_token: impl Into<String>) -> Self {
+        self.bearer_token = Some(bearer_token.into());
+        self
+    }
+
+    /// A proxy used for every request.
+    ///
+    /// By default, the standard `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables are honored.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// How failed requests are retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Whether requests check that the server supports the features they use before being sent.
+    /// See [`Ollama::set_check_capabilities`].
+    pub fn check_capabilities(mut self, check_capabilities: bool) -> Self {
+        self.check_capabilities = check_capabilities;
+        self
+    }
+
+    /// Whether requests check that the model supports the features they use before being sent.
+    /// See [`Ollama::set_validate_model_capabilities`].
+    pub fn validate_model_capabilities(mut self, validate_model_capabilities: bool) -> Self {
+        self.validate_model_capabilities = validate_model_capabilities;
+        self
+    }
+
+    /// Creates a builder configured from the environment, the same way the official CLI is.
+    ///
+    /// * `OLLAMA_HOST` - The address of the server, see [`parse_ollama_host`] for the accepted forms.
+    /// * `OLLAMA_API_KEY` - A token sent as `Authorization: Bearer <token>`.
+    /// * `OLLAMA_PROXY` - A proxy used for every request, in place of the standard proxy variables.
+    ///
+    /// Unset or empty variables are ignored.
+    pub fn from_env() -> crate::error::Result<Self> {
+        let mut builder = Self::new();
+
+        if let Some(host) = env_var("OLLAMA_HOST") {
+            builder = builder.host(parse_ollama_host(&host)?);
+        }
+        if let Some(token) = env_var("OLLAMA_API_KEY") {
+            builder = builder.bearer_token(token);
+        }
+        if let Some(proxy) = env_var("OLLAMA_PROXY") {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+
+        Ok(builder)
+    }
+
+    /// Builds the `Ollama` client.
+    ///
+    /// Returns an error if the host is not a valid URL, if the port cannot be set on it, or
+    /// if the HTTP client cannot be built (e.g. an invalid user agent).
+    pub fn build(self) -> crate::error::Result<Ollama> {
+        let mut url = Url::parse(self.host.as_deref().unwrap_or("http://127.0.0.1:11434"))?;
+
+        if let Some(port) = self.port {
+            url.set_port(Some(port))
+                .map_err(|_| url::ParseError::InvalidPort)?;
+        }
+
+        // Endpoints are appended to the URL, so its path must end with a slash
+        let path = match &self.base_path {
+            Some(base_path) => base_path.trim_matches('/').to_string(),
+            None => url.path().trim_matches('/').to_string(),
+        };
+        if path.is_empty() {
+            url.set_path("/");
+        } else {
+            url.set_path(&format!("/{}/", path));
+        }
+
+        let mut default_headers = self.default_headers;
+        if let Some(token) = self.bearer_token {
+            let mut value = HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|_| OllamaError::Other("Invalid bearer token".to_string()))?;
+            value.set_sensitive(true);
+            default_headers.insert(AUTHORIZATION, value);
+        }
+
+        let mut client = reqwest::Client::builder().default_headers(default_headers);
+        if let Some(connect_timeout) = self.connect_timeout {
+            client = client.connect_timeout(connect_timeout);
+        }
+        if let Some(user_agent) = self.user_agent {
+            client = client.user_agent(user_agent);
+        }
+        if let Some(proxy) = self.proxy {
+            client = client.proxy(proxy);
+        }
+
+        Ok(Ollama {
+            url,
+            reqwest_client: client.build()?,
+            #[cfg(feature = "headers")]
+            request_headers: HeaderMap::new(),
+            retry_policy: self.retry_policy,
+            request_timeout: self.request_timeout,
+            stream_idle_timeout: self.stream_idle_timeout,
+            default_options: self.default_options,
+            default_keep_alive: self.default_keep_alive,
+            cancellation: None,
+            commit_partial_on_cancel: false,
+            keep_thinking_in_history: false,
+            check_capabilities: self.check_capabilities,
+            server_version: Default::default(),
+            validate_model_capabilities: self.validate_model_capabilities,
+            model_capabilities: Default::default(),
+        })
+    }
+}
+
+impl Ollama {
+    /// Returns a builder to configure a new `Ollama` instance.
+    pub fn builder() -> OllamaBuilder {
+        OllamaBuilder::new()
+    }
+
+    /// Creates a new `Ollama` instance configured from the environment.
+    ///
+    /// See [`OllamaBuilder::from_env`] for the variables that are read.
+    pub fn from_env() -> crate::error::Result<Self> {
+        OllamaBuilder::from_env()?.build()
+    }
+}
+
+/// Parses a value of `OLLAMA_HOST` into the URL of the server, with the same rules as the official CLI.
+///
+/// * The scheme defaults to `http`.
+/// * The port defaults to `11434`, or to `80`/`443` when the scheme is explicitly `http`/`https`.
+/// * The host defaults to `127.0.0.1`.
+/// * Surrounding whitespace and quotes are ignored, and an invalid port is replaced by the default one.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_rs::builder::parse_ollama_host;
+///
+/// let url = parse_ollama_host("0.0.0.0").unwrap();
+/// assert_eq!(url.as_str(), "http://0.0.0.0:11434/");
+///
+/// let url = parse_ollama_host(":8080").unwrap();
+/// assert_eq!(url.as_str(), "http://127.0.0.1:8080/");
+///
+/// let url = parse_ollama_host("https://example.com/ollama").unwrap();
+/// assert_eq!(url.as_str(), "https://example.com/ollama");
+/// ```
+pub fn parse_ollama_host(value: &str) -> crate::error::Result<Url> {
+    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+
+    let (scheme, host_port, default_port) = match value.split_once("://") {
+        None => ("http", value, DEFAULT_PORT),
+        Some(("http", rest)) => ("http", rest, 80),
+        Some(("https", rest)) => ("https", rest, 443),
+        Some((scheme, rest)) => (scheme, rest, DEFAULT_PORT),
+    };
+    let (host_port, path) = host_port.split_once('/').unwrap_or((host_port, ""));
+
+    let (host, port) = match split_host_port(host_port) {
+        Some((host, port)) => {
+            let port = port.parse::<u16>().unwrap_or_else(|_| {
+                log::warn!(
+                    "Invalid port {:?} in OLLAMA_HOST, using {}",
+                    port,
+                    default_port
+                );
+                default_port
+            });
+            (host, port)
+        }
+        None => (
+            host_port.trim_start_matches('[').trim_end_matches(']'),
+            default_port,
+        ),
+    };
+    let host = if host.is_empty() { "127.0.0.1" } else { host };
+
+    // IPv6 addresses must be enclosed in brackets
+    let host = if host.contains(':') {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    };
+
+    Ok(Url::parse(&format!(
+        "{}://{}:{}/{}",
+        scheme, host, port, path
+    ))?)
+}
+
+/// Splits `host:port` or `[host]:port` like Go's `net.SplitHostPort`.
+fn split_host_port(host_port: &str) -> Option<(&str, &str)> {
+    match host_port.strip_prefix('[') {
+        Some(rest) => {
+            let (host, rest) = rest.split_once(']')?;
+            Some((host, rest.strip_prefix(':')?))
+        }
+        None => {
+            let (host, port) = host_port.rsplit_once(':')?;
+            // More than one colon is an IPv6 address without a port
+            (!host.contains(':')).then_some((host, port))
+        }
+    }
+}
+
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.trim().is_empty())
+}
@@ -0,0 +1,63 @@
+use std::future::Future;
+
+pub use tokio_util::sync::CancellationToken;
+
+use crate::{error::OllamaError, Ollama};
+
+impl Ollama {
+    /// Returns a copy of this client whose requests are aborted when `token` is cancelled.
+    ///
+    /// Cancelling the token makes pending calls return [`OllamaError::Cancelled`] and ends
+    /// open streams with that error, aborting the underlying HTTP requests.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ollama_rs::{cancellation::CancellationToken, Ollama};
+    ///
+    /// # async fn run() {
+    /// let token = CancellationToken::new();
+    /// let ollama = Ollama::default().with_cancellation(token.clone());
+    ///
+    /// // e.g. from the handler of a "stop" button
+    /// token.cancel();
+    ///
+    /// let res = ollama.pull_model("llama2:latest".to_string(), false).await;
+    /// assert!(res.is_err());
+    /// # }
+    /// ```
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        Self {
+            cancellation: Some(token),
+            ..self.clone()
+        }
+    }
+
+    /// Whether a chat stream with history commits the partial assistant message it received
+    /// when it is cancelled. (Default: false)
+    pub fn set_commit_partial_on_cancel(&mut self, commit_partial_on_cancel: bool) {
+        self.commit_partial_on_cancel = commit_partial_on_cancel;
+    }
+
+    /// Returns whether the cancellation token of this client, if any, was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Runs `future` until it completes or the cancellation token of this client is cancelled.
+    pub(crate) async fn cancellable<T>(
+        &self,
+        future: impl Future<Output = crate::error::Result<T>>,
+    ) -> crate::error::Result<T> {
+        match &self.cancellation {
+            Some(token) => tokio::select! {
+                biased;
+                _ = token.cancelled() => Err(OllamaError::Cancelled),
+                res = future => res,
+            },
+            None => future.await,
+        }
+    }
+}
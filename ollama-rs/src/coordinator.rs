@@ -1,14 +1,137 @@
+use std::{collections::HashMap, time::Duration};
+
 use crate::{
+    error::{OllamaError, ToolCallError},
     generation::{
-        chat::{request::ChatMessageRequest, ChatMessage, ChatMessageResponse, MessageRole},
+        chat::{
+            request::ChatMessageRequest, ChatMessage, ChatMessageFinalResponseData,
+            ChatMessageResponse, MessageRole,
+        },
         parameters::FormatType,
-        tools::ToolGroup,
+        tools::{ToolCall, ToolGroup},
     },
     history::ChatHistory,
     models::ModelOptions,
     Ollama,
 };
 
+/// A stream of `CoordinatorEvent` objects, see [`Coordinator::chat_stream`].
+///
+/// The stream borrows the coordinator, and is not `Send` as tools are not required to be.
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+#[cfg(feature = "stream")]
+pub type CoordinatorEventStream<'a> = std::pin::Pin<
+    Box<dyn tokio_stream::Stream<Item = crate::error::Result<CoordinatorEvent>> + 'a>,
+>;
+
+/// An event of a streamed chat with a [`Coordinator`].
+#[derive(Debug, Clone)]
+pub enum CoordinatorEvent {
+    /// A part of the reasoning of the model, when thinking is enabled, in any round.
+    Thinking(String),
+    /// A part of the answer of the model, in any round.
+    Content(String),
+    /// The model called a tool, which is being run.
+    ToolCallStarted(ToolCall),
+    /// A tool returned, and its result is sent back to the model.
+    ToolResult { call: ToolCall, result: String },
+    /// The answer of the model, once it stops calling tools.
+    Done {
+        message: ChatMessage,
+        /// The statistics of the last request.
+        final_data: Option<ChatMessageFinalResponseData>,
+    },
+}
+
+/// The system message sent along with the history when the model has to answer without tools.
+const FINAL_ANSWER_PROMPT: &str =
+    "No more tools can be called. Answer with the information you already have.";
+
+/// What the [`Coordinator`] does when the model calls a tool incorrectly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToolErrorPolicy {
+    /// Stop the chat and return the error.
+    #[default]
+    Abort,
+    /// Send the error back to the model as the result of the call, so it can correct itself.
+    ReportToModel,
+}
+
+/// Limits on the tool calls of the model in a single [`Coordinator::chat`].
+///
+/// A round is a response of the model calling tools, followed by running them.
+#[derive(Debug, Clone)]
+pub struct ToolPolicy {
+    max_rounds: usize,
+    max_calls_per_round: Option<usize>,
+    timeout: Option<Duration>,
+    tool_timeouts: HashMap<String, Duration>,
+    on_error: ToolErrorPolicy,
+    force_final_answer: bool,
+}
+
+impl Default for ToolPolicy {
+    fn default() -> Self {
+        Self {
+            max_rounds: 10,
+            max_calls_per_round: None,
+            timeout: None,
+            tool_timeouts: HashMap::new(),
+            on_error: ToolErrorPolicy::Abort,
+            force_final_answer: true,
+        }
+    }
+}
+
+impl ToolPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many rounds of tool calls are run before the model has to answer. (Default: 10)
+    pub fn max_rounds(mut self, max_rounds: usize) -> Self {
+        self.max_rounds = max_rounds;
+        self
+    }
+
+    /// How many of the tool calls of a single response are run. The model is told the others
+    /// were not. (Default: no limit)
+    pub fn max_calls_per_round(mut self, max_calls_per_round: usize) -> Self {
+        self.max_calls_per_round = Some(max_calls_per_round);
+        self
+    }
+
+    /// How long a single call of any tool can run before failing with [`ToolCallError::Timeout`].
+    /// (Default: no limit)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// How long a single call of the tool named `name` can run, overriding [`ToolPolicy::timeout`]
+    /// for this tool.
+    pub fn tool_timeout(mut self, name: impl Into<String>, timeout: Duration) -> Self {
+        self.tool_timeouts.insert(name.into(), timeout);
+        self
+    }
+
+    /// What to do when the model calls an unknown tool, passes invalid arguments, or a tool
+    /// times out. Errors returned by the tools themselves always stop the chat. (Default: [`ToolErrorPolicy::Abort`])
+    pub fn on_error(mut self, on_error: ToolErrorPolicy) -> Self {
+        self.on_error = on_error;
+        self
+    }
+
+    /// Whether the model is queried once more without tools once the rounds are exhausted, so
+    /// that it answers. Otherwise the chat fails with [`OllamaError::ToolRoundLimitExceeded`]
+    /// if the model keeps calling tools. Either way, the tool calls of the last response are
+    /// not run, and are left out of the history. (Default: true)
+    pub fn force_final_answer(mut self, force_final_answer: bool) -> Self {
+        self.force_final_answer = force_final_answer;
+        self
+    }
+}
+
 /// A coordinator for managing chat interactions and tool usage.
 ///
 /// This struct is responsible for coordinating chat messages and tool
@@ -22,6 +145,7 @@ pub struct Coordinator<C: ChatHistory, T: ToolGroup> {
     tools: T,
     debug: bool,
     format: Option<FormatType>,
+    tool_policy: ToolPolicy,
 }
 
 impl<C: ChatHistory> Coordinator<C, ()> {
@@ -36,15 +160,16 @@ impl<C: ChatHistory> Coordinator<C, ()> {
     /// # Returns
     ///
     /// A new `Coordinator` instance.
-    pub fn new(ollama: Ollama, model: String, history: C) -> Self {
+    pub fn new(ollama: Ollama, model: impl Into<String>, history: C) -> Self {
         Self {
-            model,
+            model: model.into(),
             ollama,
             options: ModelOptions::default(),
             history,
             tools: (),
             debug: false,
             format: None,
+            tool_policy: ToolPolicy::default(),
         }
     }
 }
@@ -62,15 +187,16 @@ impl<C: ChatHistory, T: ToolGroup> Coordinator<C, T> {
     /// # Returns
     ///
     /// A new `Coordinator` instance with tools.
-    pub fn new_with_tools(ollama: Ollama, model: String, history: C, tools: T) -> Self {
+    pub fn new_with_tools(ollama: Ollama, model: impl Into<String>, history: C, tools: T) -> Self {
         Self {
-            model,
+            model: model.into(),
             ollama,
             options: ModelOptions::default(),
             history,
             tools,
             debug: false,
             format: None,
+            tool_policy: ToolPolicy::default(),
         }
     }
 
@@ -89,30 +215,150 @@ impl<C: ChatHistory, T: ToolGroup> Coordinator<C, T> {
         self
     }
 
+    /// Limits on the tool calls of the model, see [`ToolPolicy`].
+    pub fn tool_policy(mut self, tool_policy: ToolPolicy) -> Self {
+        self.tool_policy = tool_policy;
+        self
+    }
+
+    /// Adds the messages to the history and queries the model, running the tools it calls and
+    /// querying it again with their results until it answers, within the limits of the [`ToolPolicy`].
     pub async fn chat(
         &mut self,
         messages: Vec<ChatMessage>,
     ) -> crate::error::Result<ChatMessageResponse> {
-        if self.debug {
-            for m in &messages {
-                eprintln!("Hit {} with:", self.model);
-                eprintln!("\t{:?}: '{}'", m.role, m.content);
+        self.debug_messages(&messages);
+
+        for m in messages {
+            self.history.push(m);
+        }
+
+        let mut round = 0;
+        loop {
+            let request = self.request(round);
+            let mut resp = self.ollama.send_chat_messages(request).await?;
+
+            if self.is_last_response(round, &resp.message) {
+                self.finish(&mut resp.message)?;
+                self.debug_response(&resp.message);
+
+                return Ok(resp);
             }
+            let mut results = Vec::with_capacity(resp.message.tool_calls.len());
+            for (index, call) in resp.message.tool_calls.iter().enumerate() {
+                results.push(self.call_tool(call, index).await?);
+            }
+            self.push_round(resp.message, results);
+
+            round += 1;
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+    #[cfg(feature = "stream")]
+    /// Same as [`Coordinator::chat`], streaming the answer of the model and the tool calls as they happen.
+    ///
+    /// The messages are added to the history as in [`Coordinator::chat`]. The stream ends after
+    /// [`CoordinatorEvent::Done`], or after the first error.
+    pub fn chat_stream(&mut self, messages: Vec<ChatMessage>) -> CoordinatorEventStream<'_> {
+        use async_stream::stream;
+        use tokio_stream::StreamExt;
+
+        use crate::generation::chat::events::{chat_stream_events, ChatStreamEvent};
+
+        self.debug_messages(&messages);
+
+        for m in messages {
+            self.history.push(m);
         }
 
-        let mut request = ChatMessageRequest::new(self.model.clone(), messages)
-            .options(self.options.clone())
-            .tools::<T>();
+        Box::pin(stream! {
+            let mut round = 0;
+            'rounds: loop {
+                let request = self.request(round);
+                let mut events = match self.ollama.send_chat_messages_stream(request).await {
+                    Ok(responses) => chat_stream_events(responses),
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                };
 
-        if let Some(format) = &self.format {
-            let mut tools = vec![];
-            T::tool_info(&mut tools);
+                let mut done = None;
+                while let Some(event) = events.next().await {
+                    match event {
+                        Ok(ChatStreamEvent::Thinking(thinking)) => {
+                            yield Ok(CoordinatorEvent::Thinking(thinking));
+                        }
+                        Ok(ChatStreamEvent::Content(content)) => {
+                            yield Ok(CoordinatorEvent::Content(content));
+                        }
+                        // Run once the whole message is received
+                        Ok(ChatStreamEvent::ToolCall(_)) => {}
+                        Ok(ChatStreamEvent::Done { message, final_data }) => {
+                            done = Some((message, final_data));
+                        }
+                        Err(e) => {
+                            yield Err(e);
+                            break 'rounds;
+                        }
+                    }
+                }
+
+                let Some((mut message, final_data)) = done else {
+                    yield Err(OllamaError::Other(
+                        "The chat stream ended before the response was done".to_string(),
+                    ));
+                    break;
+                };
+                if self.is_last_response(round, &message) {
+                    if let Err(e) = self.finish(&mut message) {
+                        yield Err(e);
+                        break;
+                    }
+                    self.debug_response(&message);
+                    yield Ok(CoordinatorEvent::Done { message, final_data });
+                    break;
+                }
+                let mut results = Vec::with_capacity(message.tool_calls.len());
+                for (index, call) in message.tool_calls.iter().enumerate() {
+                    yield Ok(CoordinatorEvent::ToolCallStarted(call.clone()));
+
+                    match self.call_tool(call, index).await {
+                        Ok(result) => {
+                            results.push(result.clone());
+                            yield Ok(CoordinatorEvent::ToolResult { call: call.clone(), result });
+                        }
+                        Err(e) => {
+                            yield Err(e);
+                            break 'rounds;
+                        }
+                    }
+                }
+                self.push_round(message, results);
+
+                round += 1;
+            }
+        })
+    }
+
+    /// Builds the request of the given round from the history.
+    fn request(&self, round: usize) -> ChatMessageRequest {
+        let mut messages = self.history.messages().to_vec();
+        let mut request = if self.is_final_answer(round) {
+            messages.push(ChatMessage::system(FINAL_ANSWER_PROMPT.to_string()));
+            ChatMessageRequest::new(self.model.clone(), messages)
+        } else {
+            ChatMessageRequest::new(self.model.clone(), messages).tools::<T>()
+        };
+        request = request.options(self.options.clone());
 
+        if let Some(format) = &self.format {
             // If no tools are specified, set the format on the request. Otherwise wait for the
-            // recursive call by checking that the last message in the history has a Tool role,
+            // next round by checking that the last message in the history has a Tool role,
             // before setting the format. Ollama otherwise won't call the tool if the format
             // is set on the first request.
-            if tools.is_empty() {
+            if request.tools.is_empty() {
                 request = request.format(format.clone());
             } else if let Some(last_message) = self.history.messages().last() {
                 if last_message.role == MessageRole::Tool {
@@ -121,37 +367,120 @@ impl<C: ChatHistory, T: ToolGroup> Coordinator<C, T> {
             }
         }
 
-        let resp = self
-            .ollama
-            .send_chat_messages_with_history(&mut self.history, request)
-            .await?;
+        request
+    }
 
-        if !resp.message.tool_calls.is_empty() {
-            for call in resp.message.tool_calls {
-                if self.debug {
-                    eprintln!("Tool call: {:?}", call.function);
-                }
+    /// Whether the model has to answer without tools in this round.
+    fn is_final_answer(&self, round: usize) -> bool {
+        round >= self.tool_policy.max_rounds && self.tool_policy.force_final_answer
+    }
+
+    /// Whether `message`, the response of the given round, ends the chat.
+    fn is_last_response(&self, round: usize, message: &ChatMessage) -> bool {
+        message.tool_calls.is_empty() || round >= self.tool_policy.max_rounds
+    }
+
+    /// Adds the last response of the model to the history. The tool calls it still makes are not
+    /// run, so they are dropped rather than left without results in the history.
+    fn finish(&mut self, message: &mut ChatMessage) -> crate::error::Result<()> {
+        let calls_tools = !message.tool_calls.is_empty();
+        message.tool_calls.clear();
+        self.push_response(message.clone());
+
+        if calls_tools && !self.tool_policy.force_final_answer {
+            return Err(OllamaError::ToolRoundLimitExceeded(
+                self.tool_policy.max_rounds,
+            ));
+        }
+        Ok(())
+    }
 
-                let resp = self.tools.call(&call.function).await?;
+    /// Adds a response calling tools to the history along with the results of its calls. This is
+    /// only done once every call has a result, so that a failed round leaves no call without one.
+    fn push_round(&mut self, message: ChatMessage, results: Vec<String>) {
+        self.push_response(message);
+        for result in results {
+            self.history.push(ChatMessage::tool(result));
+        }
+    }
 
-                if self.debug {
-                    eprintln!("Tool response: {}", &resp);
+    fn push_response(&mut self, mut message: ChatMessage) {
+        if !self.ollama.keep_thinking_in_history {
+            message.thinking = None;
+        }
+        self.history.push(message);
+    }
+
+    /// Runs the `index`-th tool call of a response, and returns the result sent back to the model.
+    async fn call_tool(&mut self, call: &ToolCall, index: usize) -> crate::error::Result<String> {
+        if self.debug {
+            eprintln!("Tool call: {:?}", call.function);
+        }
+
+        let name = call.function.name();
+        if let Some(max) = self
+            .tool_policy
+            .max_calls_per_round
+            .filter(|max| index >= *max)
+        {
+            return Ok(format!(
+                "Error: {} was not called, as at most {} tools can be called at once",
+                name, max
+            ));
+        }
+
+        let timeout = self
+            .tool_policy
+            .tool_timeouts
+            .get(name)
+            .copied()
+            .or(self.tool_policy.timeout);
+        let result = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.tools.call(&call.function))
+                .await
+                .unwrap_or(Err(ToolCallError::Timeout(timeout))),
+            None => self.tools.call(&call.function).await,
+        };
+
+        let resp = match result {
+            Ok(resp) => resp,
+            Err(e) if self.tool_policy.on_error == ToolErrorPolicy::ReportToModel => match e {
+                ToolCallError::UnknownToolName => {
+                    format!("Error: there is no tool named {}", name)
+                }
+                ToolCallError::InvalidToolArguments(e) => {
+                    format!("Error: invalid arguments for {}: {}", name, e)
                 }
+                ToolCallError::Timeout(timeout) => {
+                    format!("Error: {} did not return within {:?}", name, timeout)
+                }
+                e => return Err(e.into()),
+            },
+            Err(e) => return Err(e.into()),
+        };
 
-                self.history.push(ChatMessage::tool(resp))
-            }
+        if self.debug {
+            eprintln!("Tool response: {}", &resp);
+        }
 
-            // recurse
-            Box::pin(self.chat(vec![])).await
-        } else {
-            if self.debug {
-                eprintln!(
-                    "Response from {} of type {:?}: '{}'",
-                    resp.model, resp.message.role, resp.message.content
-                );
+        Ok(resp)
+    }
+
+    fn debug_messages(&self, messages: &[ChatMessage]) {
+        if self.debug {
+            for m in messages {
+                eprintln!("Hit {} with:", self.model);
+                eprintln!("\t{:?}: '{}'", m.role, m.content);
             }
+        }
+    }
 
-            Ok(resp)
+    fn debug_response(&self, message: &ChatMessage) {
+        if self.debug {
+            eprintln!(
+                "Response from {} of type {:?}: '{}'",
+                self.model, message.role, message.content
+            );
         }
     }
 }
@@ -1,7 +1,15 @@
+use std::{fmt::Display, time::Duration};
+
+use reqwest::StatusCode;
 use serde::Deserialize;
 use static_assertions::assert_impl_all;
 use thiserror::Error;
 
+use crate::{
+    models::ModelCapability,
+    version::{Capability, OllamaVersion},
+};
+
 assert_impl_all!(OllamaError: Send, Sync);
 /// A result type for operations in the ollama-rs crate.
 ///
@@ -23,6 +31,35 @@ pub enum OllamaError {
     ReqwestError(#[from] reqwest::Error),
     #[error("Internal Ollama error")]
     InternalError(InternalOllamaError),
+    #[error("Ollama API error: {0}")]
+    ApiError(#[from] ApiError),
+    #[error("I/O error")]
+    IoError(#[from] std::io::Error),
+    #[error("Invalid model name {0}")]
+    InvalidModelName(String),
+    #[error("Invalid Ollama URL")]
+    UrlError(#[from] url::ParseError),
+    #[error("No data received from the stream for {0:?}")]
+    StreamIdleTimeout(Duration),
+    #[error("Request cancelled")]
+    Cancelled,
+    #[error("{capability} requires Ollama {} or greater, but the server is running {server_version}", capability.min_version())]
+    UnsupportedCapability {
+        capability: Capability,
+        server_version: OllamaVersion,
+    },
+    #[error("Invalid model option {option}: {reason}")]
+    InvalidModelOption {
+        option: &'static str,
+        reason: String,
+    },
+    #[error("The model {model} does not support {capability}")]
+    UnsupportedModelCapability {
+        model: String,
+        capability: ModelCapability,
+    },
+    #[error("The model was still calling tools after {0} rounds")]
+    ToolRoundLimitExceeded(usize),
     #[error("Error in Ollama")]
     Other(String),
 }
@@ -36,6 +73,144 @@ pub struct InternalOllamaError {
     pub message: String,
 }
 
+/// An error response returned by the Ollama API.
+///
+/// Every endpoint returns this when the server answers with a non-success status code.
+/// The `kind` is classified from the status code and the server message, so callers can
+/// branch on failures without matching on the message text.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// The HTTP status code of the response.
+    pub status: StatusCode,
+    /// The error message sent by the server, or the raw body if it was not a JSON error.
+    pub message: String,
+    /// The classified kind of the error.
+    pub kind: ApiErrorKind,
+    /// How long the server asked us to wait before retrying, from the `Retry-After` header.
+    pub retry_after: Option<Duration>,
+}
+
+impl ApiError {
+    /// Creates a new `ApiError`, classifying it from the status code and message.
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        let message = message.into();
+        let kind = ApiErrorKind::classify(status, &message);
+
+        Self {
+            status,
+            message,
+            kind,
+            retry_after: None,
+        }
+    }
+
+    /// Builds an `ApiError` from a non-success response, consuming its body.
+    pub(crate) async fn from_response(res: reqwest::Response) -> Self {
+        let status = res.status();
+        let retry_after = res
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs);
+
+        let body = res.text().await.unwrap_or_else(|e| e.to_string());
+        let message = match serde_json::from_str::<InternalOllamaError>(&body) {
+            Ok(err) => err.message,
+            Err(_) if body.trim().is_empty() => status
+                .canonical_reason()
+                .unwrap_or("Unknown error")
+                .to_string(),
+            Err(_) => body,
+        };
+
+        Self {
+            retry_after,
+            ..Self::new(status, message)
+        }
+    }
+
+    /// Whether the request that caused this error may succeed if sent again later.
+    pub fn is_retryable(&self) -> bool {
+        self.kind.is_retryable()
+    }
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({:?}): {}", self.status, self.kind, self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// The kind of an [`ApiError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiErrorKind {
+    /// The request was malformed or contained invalid values.
+    BadRequest,
+    /// The server requires authentication.
+    Unauthorized,
+    /// The server refused to perform the request.
+    Forbidden,
+    /// The requested model does not exist locally or in the registry.
+    ModelNotFound,
+    /// The requested resource does not exist.
+    NotFound,
+    /// The input does not fit in the context window of the model.
+    ContextTooLong,
+    /// The server does not have enough memory to load the model.
+    OutOfMemory,
+    /// The server is busy or rate limiting requests.
+    ServerOverloaded,
+    /// Any other server-side failure.
+    ServerError,
+    /// An error that does not fit any other kind.
+    Other,
+}
+
+impl ApiErrorKind {
+    /// Classifies an error from its HTTP status code and the message returned by the server.
+    pub fn classify(status: StatusCode, message: &str) -> Self {
+        let message = message.to_lowercase();
+
+        if message.contains("model") && message.contains("not found")
+            || message.contains("file does not exist")
+        {
+            Self::ModelNotFound
+        } else if message.contains("context length")
+            || message.contains("context window")
+            || message.contains("input length exceeds")
+        {
+            Self::ContextTooLong
+        } else if message.contains("out of memory")
+            || message.contains("more system memory")
+            || message.contains("insufficient memory")
+        {
+            Self::OutOfMemory
+        } else if status == StatusCode::SERVICE_UNAVAILABLE
+            || status == StatusCode::TOO_MANY_REQUESTS
+            || message.contains("server busy")
+        {
+            Self::ServerOverloaded
+        } else {
+            match status {
+                StatusCode::BAD_REQUEST => Self::BadRequest,
+                StatusCode::UNAUTHORIZED => Self::Unauthorized,
+                StatusCode::FORBIDDEN => Self::Forbidden,
+                StatusCode::NOT_FOUND => Self::NotFound,
+                s if s.is_server_error() => Self::ServerError,
+                _ => Self::Other,
+            }
+        }
+    }
+
+    /// Whether errors of this kind are usually transient.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::ServerOverloaded | Self::ServerError)
+    }
+}
+
 /// An error type for tool call operations.
 ///
 /// This enum represents errors that can occur when calling tools within the Ollama service.
@@ -50,4 +225,6 @@ pub enum ToolCallError {
     InvalidToolArguments(#[from] serde_json::Error),
     #[error("Tool errored internally when it was called")]
     InternalToolError(#[from] Box<dyn std::error::Error + Send + Sync>),
+    #[error("Tool did not return within {0:?}")]
+    Timeout(Duration),
 }
@@ -2,10 +2,11 @@
 ///
 /// This file aggregates various submodules that handle different aspects
 /// of generation tasks, including chat, completion, embeddings, images,
-/// options, parameters, and tools.
+/// log probabilities, options, parameters, and tools.
 pub mod chat;
 pub mod completion;
 pub mod embeddings;
 pub mod images;
+pub mod logprobs;
 pub mod parameters;
 pub mod tools;
@@ -0,0 +1,98 @@
+use super::{ChatMessage, ChatMessageFinalResponseData};
+use crate::generation::tools::ToolCall;
+
+/// A stream of `ChatStreamEvent` objects, see [`chat_stream_events`].
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+#[cfg(feature = "stream")]
+pub type ChatStreamEventStream = std::pin::Pin<
+    Box<dyn tokio_stream::Stream<Item = crate::error::Result<ChatStreamEvent>> + Send>,
+>;
+
+/// A part of a streamed chat response.
+#[derive(Debug, Clone)]
+pub enum ChatStreamEvent {
+    /// A part of the reasoning of the model, when thinking is enabled.
+    Thinking(String),
+    /// A part of the answer of the model.
+    Content(String),
+    /// A call to one of the tools of the request. Tool calls are never split across events.
+    ToolCall(ToolCall),
+    /// The end of the response.
+    Done {
+        /// The whole assistant message, with its reasoning and tool calls.
+        message: ChatMessage,
+        final_data: Option<ChatMessageFinalResponseData>,
+    },
+}
+
+/// Turns a chat stream, such as the one of [`crate::Ollama::send_chat_messages_stream`], into
+/// a stream of its reasoning, content and tool calls, ending with the whole message.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_rs::generation::chat::{
+///     events::{chat_stream_events, ChatStreamEvent},
+///     request::ChatMessageRequest,
+///     ChatMessage,
+/// };
+/// use ollama_rs::Ollama;
+/// use tokio_stream::StreamExt;
+///
+/// # async fn run() -> ollama_rs::error::Result<()> {
+/// let ollama = Ollama::default();
+/// let request = ChatMessageRequest::new("llama3.2", vec![ChatMessage::user("Hi".to_string())]);
+///
+/// let mut events = chat_stream_events(ollama.send_chat_messages_stream(request).await?);
+/// while let Some(event) = events.next().await {
+///     match event? {
+///         ChatStreamEvent::Content(text) => print!("{}", text),
+///         ChatStreamEvent::ToolCall(call) => println!("Calling {}", call.function.name()),
+///         _ => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+#[cfg(feature = "stream")]
+pub fn chat_stream_events(stream: super::ChatMessageResponseStream) -> ChatStreamEventStream {
+    use async_stream::stream;
+    use tokio_stream::StreamExt;
+
+    use super::ChatMessageAccumulator;
+
+    let mut stream = stream;
+
+    Box::pin(stream! {
+        let mut accumulator = ChatMessageAccumulator::new();
+
+        while let Some(response) = stream.next().await {
+            let response = match response {
+                Ok(response) => response,
+                Err(e) => {
+                    yield Err(e);
+                    break;
+                }
+            };
+            accumulator.push(&response);
+
+            if let Some(thinking) = response.message.thinking.filter(|t| !t.is_empty()) {
+                yield Ok(ChatStreamEvent::Thinking(thinking));
+            }
+            if !response.message.content.is_empty() {
+                yield Ok(ChatStreamEvent::Content(response.message.content));
+            }
+            for tool_call in response.message.tool_calls {
+                yield Ok(ChatStreamEvent::ToolCall(tool_call));
+            }
+
+            if response.done {
+                yield Ok(ChatStreamEvent::Done {
+                    message: accumulator.message(),
+                    final_data: response.final_data,
+                });
+            }
+        }
+    })
+}
@@ -1,25 +1,25 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{error::OllamaError, history::ChatHistory, Ollama};
+use crate::{history::ChatHistory, Ollama};
+pub mod events;
 pub mod request;
-use super::{images::Image, tools::ToolCall};
+use super::{
+    images::Image,
+    logprobs::{validate_top_logprobs, TokenLogprob},
+    tools::ToolCall,
+};
 use request::ChatMessageRequest;
 
-#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
-#[cfg(feature = "stream")]
-use async_stream::stream;
 #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
 #[cfg(feature = "stream")]
 use std::sync::{Arc, Mutex};
-#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
-#[cfg(feature = "stream")]
-use tokio_stream::StreamExt;
 
 #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
 #[cfg(feature = "stream")]
 /// A stream of `ChatMessageResponse` objects
-pub type ChatMessageResponseStream =
-    std::pin::Pin<Box<dyn tokio_stream::Stream<Item = Result<ChatMessageResponse, ()>> + Send>>;
+pub type ChatMessageResponseStream = std::pin::Pin<
+    Box<dyn tokio_stream::Stream<Item = crate::error::Result<ChatMessageResponse>> + Send>,
+>;
 
 impl Ollama {
     #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
@@ -32,84 +32,27 @@ impl Ollama {
     ) -> crate::error::Result<ChatMessageResponseStream> {
         let mut request = request;
         request.stream = true;
+        request.options = self.request_options(request.options)?;
+        validate_top_logprobs(request.top_logprobs)?;
+        request.keep_alive = request
+            .keep_alive
+            .or_else(|| self.default_keep_alive.clone());
+
+        self.check_capabilities(&request.required_capabilities())
+            .await?;
+        self.validate_model_capabilities(
+            &request.model_name,
+            &request.required_model_capabilities(),
+        )
+        .await?;
 
         let url = format!("{}api/chat", self.url_str());
-        let serialized = serde_json::to_string(&request)
-            .map_err(|e| e.to_string())
-            .unwrap();
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
-
-        let res = builder.body(serialized).send().await?;
-
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(
-                res.text().await.unwrap_or_else(|e| e.to_string()),
-            ));
-        }
-
-        let s = stream! {
-            let mut buffer = String::new();
-
-            let mut stream = res.bytes_stream();
-            while let Some(chunk_result) = stream.next().await {
-                match chunk_result {
-                    Ok(chunk) => {
-                        // Convert bytes to string and append to buffer
-                        if let Ok(chunk_str) = String::from_utf8(chunk.to_vec()) {
-                            buffer.push_str(&chunk_str);
-
-                            // Process complete lines in the buffer
-                            let mut lines_to_process = Vec::new();
-                            let mut start_pos = 0;
-
-                            // Find all complete lines in the buffer and collect them
-                            while let Some(pos) = buffer[start_pos..].find('\n') {
-                                let actual_pos = start_pos + pos;
-                                let line = buffer[start_pos..actual_pos].trim().to_string();
-                                if !line.is_empty() {
-                                    lines_to_process.push(line);
-                                }
-                                start_pos = actual_pos + 1;
-                            }
-
-                            // If we processed any lines, truncate the buffer
-                            if start_pos > 0 {
-                                buffer = buffer[start_pos..].to_string();
-                            }
-
-                            // Process all collected lines
-                            for line in lines_to_process {
-                                // Parse the JSON line
-                                match serde_json::from_str::<ChatMessageResponse>(&line) {
-                                    Ok(response) => yield Ok(response),
-                                    Err(e) => {
-                                        eprintln!("Failed to deserialize response: {}", e);
-                                        // Continue processing other lines even if one fails
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to read response: {}", e);
-                        yield Err(());
-                        break;
-                    }
-                }
-            }
+        let serialized = serde_json::to_string(&request)?;
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-            // Process any remaining data in the buffer
-            if !buffer.is_empty() {
-                if let Ok(response) = serde_json::from_str::<ChatMessageResponse>(&buffer) {
-                    yield Ok(response);
-                }
-            }
-        };
+        let res = self.send_stream_request(builder, true).await?;
 
-        Ok(Box::pin(s))
+        Ok(self.ndjson_stream(res))
     }
 
     /// Chat message generation.
@@ -120,23 +63,25 @@ impl Ollama {
     ) -> crate::error::Result<ChatMessageResponse> {
         let mut request = request;
         request.stream = false;
+        request.options = self.request_options(request.options)?;
+        validate_top_logprobs(request.top_logprobs)?;
+        request.keep_alive = request
+            .keep_alive
+            .or_else(|| self.default_keep_alive.clone());
+
+        self.check_capabilities(&request.required_capabilities())
+            .await?;
+        self.validate_model_capabilities(
+            &request.model_name,
+            &request.required_model_capabilities(),
+        )
+        .await?;
 
         let url = format!("{}api/chat", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
-
-        let res = builder.body(serialized).send().await?;
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(
-                res.text().await.unwrap_or_else(|e| e.to_string()),
-            ));
-        }
-
-        let bytes = res.bytes().await?;
+        let bytes = self.send_request(builder, true).await?;
         let res = serde_json::from_slice::<ChatMessageResponse>(&bytes)?;
 
         Ok(res)
@@ -146,6 +91,11 @@ impl Ollama {
 impl Ollama {
     #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
     #[cfg(feature = "stream")]
+    /// Chat message generation with streaming, saving the messages in the history.
+    /// The assistant message is added to the history once the stream is done, along with its tool calls.
+    /// If the stream fails, the error is yielded last and no assistant message is added.
+    /// If the client is cancelled, the partial answer is only added when enabled with
+    /// [`Ollama::set_commit_partial_on_cancel`].
     pub async fn send_chat_messages_with_history_stream<C: ChatHistory + Send + 'static>(
         &self,
         history: Arc<Mutex<C>>,
@@ -168,16 +118,32 @@ impl Ollama {
         let mut resp_stream: ChatMessageResponseStream =
             self.send_chat_messages_stream(request.clone()).await?;
 
+        let cancellation = self.cancellation.clone();
+        let commit_partial_on_cancel = self.commit_partial_on_cancel;
+        let keep_thinking = self.keep_thinking_in_history;
+
         let s = stream! {
-            let mut result = String::new();
+            let mut result = ChatMessageAccumulator::new();
+
+            while let Some(item) = resp_stream.next().await {
+                let item = match item {
+                    Ok(item) => item,
+                    Err(e) => {
+                        // A cancelled answer is only kept if explicitly asked for
+                        let cancelled = cancellation.as_ref().is_some_and(|t| t.is_cancelled());
+                        let partial = !result.content().is_empty() || !result.tool_calls().is_empty();
+                        if cancelled && commit_partial_on_cancel && partial {
+                            history.lock().unwrap().push(result.history_message(keep_thinking));
+                        }
 
-            while let Some(item) = resp_stream.try_next().await.unwrap() {
-                let msg_part = item.clone().message.content;
+                        yield Err(e);
+                        break;
+                    }
+                };
 
+                result.push(&item);
                 if item.done {
-                    history.lock().unwrap().push(ChatMessage::assistant(result.clone()));
-                } else {
-                    result.push_str(&msg_part);
+                    history.lock().unwrap().push(result.history_message(keep_thinking));
                 }
 
                 yield Ok(item);
@@ -204,13 +170,25 @@ impl Ollama {
         let result = self.send_chat_messages(request.clone()).await;
 
         if let Ok(result) = result {
-            history.push(result.message.clone());
+            let mut message = result.message.clone();
+            if !self.keep_thinking_in_history {
+                message.thinking = None;
+            }
+            history.push(message);
 
             return Ok(result);
         }
 
         result
     }
+
+    /// Whether the reasoning of the model is kept in the history along with its answers.
+    ///
+    /// It is then sent back to the model with the next messages, which takes room in the context
+    /// window. The responses always contain the reasoning. (Default: false)
+    pub fn set_keep_thinking_in_history(&mut self, keep_thinking_in_history: bool) {
+        self.keep_thinking_in_history = keep_thinking_in_history;
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -222,11 +200,116 @@ pub struct ChatMessageResponse {
     /// The generated chat message.
     pub message: ChatMessage,
     pub done: bool,
+    /// The log probabilities of the generated tokens, when requested. If the completion is streaming, these are only the ones of this response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<Vec<TokenLogprob>>,
     #[serde(flatten)]
     /// The final data of the completion. This is only present if the completion is done.
     pub final_data: Option<ChatMessageFinalResponseData>,
 }
 
+/// Accumulates the responses of a chat stream into the full message, keeping the reasoning
+/// of the model separate from its answer, and collecting its tool calls.
+///
+/// See [`events::chat_stream_events`] to handle each part of the stream separately instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_rs::generation::chat::{request::ChatMessageRequest, ChatMessage, ChatMessageAccumulator};
+/// use ollama_rs::Ollama;
+/// use tokio_stream::StreamExt;
+///
+/// # async fn run() -> ollama_rs::error::Result<()> {
+/// let ollama = Ollama::default();
+/// let request = ChatMessageRequest::new("qwen3", vec![ChatMessage::user("Why is the sky blue?".to_string())])
+///     .think(true);
+///
+/// let mut stream = ollama.send_chat_messages_stream(request).await?;
+/// let mut accumulator = ChatMessageAccumulator::new();
+/// while let Some(res) = stream.next().await {
+///     accumulator.push(&res?);
+/// }
+///
+/// println!("Thought: {}", accumulator.thinking());
+/// println!("Answer: {}", accumulator.content());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChatMessageAccumulator {
+    content: String,
+    thinking: String,
+    tool_calls: Vec<ToolCall>,
+    logprobs: Vec<TokenLogprob>,
+    done: bool,
+}
+
+impl ChatMessageAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a response of the stream.
+    pub fn push(&mut self, response: &ChatMessageResponse) {
+        self.content.push_str(&response.message.content);
+        if let Some(thinking) = &response.message.thinking {
+            self.thinking.push_str(thinking);
+        }
+        self.tool_calls
+            .extend(response.message.tool_calls.iter().cloned());
+        if let Some(logprobs) = &response.logprobs {
+            self.logprobs.extend(logprobs.iter().cloned());
+        }
+        self.done |= response.done;
+    }
+
+    /// The answer received so far.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// The reasoning received so far.
+    pub fn thinking(&self) -> &str {
+        &self.thinking
+    }
+
+    /// The tool calls received so far. Each one is received whole, in a single response.
+    pub fn tool_calls(&self) -> &[ToolCall] {
+        &self.tool_calls
+    }
+
+    /// The log probabilities of the tokens received so far, when requested.
+    pub fn logprobs(&self) -> &[TokenLogprob] {
+        &self.logprobs
+    }
+
+    /// Whether the last response of the stream was received.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// The assistant message received so far, with its tool calls.
+    pub fn message(&self) -> ChatMessage {
+        let mut message = ChatMessage::assistant(self.content.clone());
+        message.tool_calls = self.tool_calls.clone();
+        if !self.thinking.is_empty() {
+            message.thinking = Some(self.thinking.clone());
+        }
+        message
+    }
+
+    /// The message to add to the history, with or without the reasoning.
+    #[cfg(feature = "stream")]
+    fn history_message(&self, keep_thinking: bool) -> ChatMessage {
+        let mut message = self.message();
+        if !keep_thinking {
+            message.thinking = None;
+        }
+        message
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessageFinalResponseData {
     /// Time spent generating the response
@@ -245,6 +328,9 @@ pub struct ChatMessageFinalResponseData {
 pub struct ChatMessage {
     pub role: MessageRole,
     pub content: String,
+    /// The reasoning of the model before its answer, when thinking is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>,
     #[serde(default)]
     pub tool_calls: Vec<ToolCall>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -256,6 +342,7 @@ impl ChatMessage {
         Self {
             role,
             content,
+            thinking: None,
             tool_calls: vec![],
             images: None,
         }
@@ -282,6 +369,11 @@ impl ChatMessage {
         self
     }
 
+    pub fn with_thinking(mut self, thinking: String) -> Self {
+        self.thinking = Some(thinking);
+        self
+    }
+
     pub fn add_image(mut self, image: Image) -> Self {
         if let Some(images) = self.images.as_mut() {
             images.push(image);
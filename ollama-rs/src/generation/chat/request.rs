@@ -2,10 +2,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     generation::{
-        parameters::FormatType,
+        parameters::{FormatType, KeepAlive, Think},
         tools::{ToolGroup, ToolInfo},
     },
-    models::ModelOptions,
+    models::{ModelCapability, ModelOptions},
+    version::Capability,
 };
 
 use super::ChatMessage;
@@ -28,19 +29,35 @@ pub struct ChatMessageRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub format: Option<FormatType>,
-    /// Must be false if tools are provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_deserializing)]
+    pub keep_alive: Option<KeepAlive>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub think: Option<Think>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub logprobs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub top_logprobs: Option<u32>,
+    /// Set by the send methods. Streaming with tools requires Ollama 0.8.0 or greater.
     #[serde(default)]
     pub(crate) stream: bool,
 }
 
 impl ChatMessageRequest {
-    pub fn new(model_name: String, messages: Vec<ChatMessage>) -> Self {
+    pub fn new(model_name: impl Into<String>, messages: Vec<ChatMessage>) -> Self {
         Self {
-            model_name,
+            model_name: model_name.into(),
             messages,
             options: None,
             template: None,
             format: None,
+            keep_alive: None,
+            think: None,
+            logprobs: None,
+            top_logprobs: None,
             // Stream value will be overwritten by Ollama::send_chat_messages_stream() and Ollama::send_chat_messages() methods
             stream: false,
             tools: vec![],
@@ -65,6 +82,33 @@ impl ChatMessageRequest {
         self
     }
 
+    /// Used to control how long a model stays loaded in memory, by default models are unloaded after 5 minutes of inactivity
+    pub fn keep_alive(mut self, keep_alive: KeepAlive) -> Self {
+        self.keep_alive = Some(keep_alive);
+        self
+    }
+
+    /// Whether a reasoning model thinks before answering. The reasoning is returned in the `thinking`
+    /// field of the messages, separately from their content.
+    pub fn think(mut self, think: impl Into<Think>) -> Self {
+        self.think = Some(think.into());
+        self
+    }
+
+    /// Return the log probability of each generated token, see [`crate::generation::logprobs::TokenLogprob`].
+    pub fn logprobs(mut self, logprobs: bool) -> Self {
+        self.logprobs = Some(logprobs);
+        self
+    }
+
+    /// Also return the `top_logprobs` most likely tokens at each position, at most
+    /// [`crate::generation::logprobs::MAX_TOP_LOGPROBS`]. This enables [`Self::logprobs`].
+    pub fn top_logprobs(mut self, top_logprobs: u32) -> Self {
+        self.logprobs = Some(true);
+        self.top_logprobs = Some(top_logprobs);
+        self
+    }
+
     /// Tools that are available to the LLM.
     pub fn tools<T: ToolGroup>(mut self) -> Self {
         self.tools.clear();
@@ -72,4 +116,41 @@ impl ChatMessageRequest {
 
         self
     }
+
+    /// The capabilities the server needs to handle this request.
+    pub(crate) fn required_capabilities(&self) -> Vec<Capability> {
+        let mut capabilities = vec![];
+        if !self.tools.is_empty() {
+            capabilities.push(Capability::Tools);
+            if self.stream {
+                capabilities.push(Capability::StreamingToolCalls);
+            }
+        }
+        if matches!(self.format, Some(FormatType::StructuredJson(_))) {
+            capabilities.push(Capability::StructuredOutputs);
+        }
+        if self.think.is_some() {
+            capabilities.push(Capability::Thinking);
+        }
+        capabilities
+    }
+
+    /// The capabilities the model needs to handle this request.
+    pub(crate) fn required_model_capabilities(&self) -> Vec<ModelCapability> {
+        let mut capabilities = vec![ModelCapability::Completion];
+        if !self.tools.is_empty() {
+            capabilities.push(ModelCapability::Tools);
+        }
+        if self
+            .messages
+            .iter()
+            .any(|m| m.images.as_ref().is_some_and(|i| !i.is_empty()))
+        {
+            capabilities.push(ModelCapability::Vision);
+        }
+        if self.think.is_some_and(|t| t.is_enabled()) {
+            capabilities.push(ModelCapability::Thinking);
+        }
+        capabilities
+    }
 }
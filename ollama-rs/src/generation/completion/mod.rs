@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{error::OllamaError, Ollama};
+use crate::{
+    generation::logprobs::{validate_top_logprobs, TokenLogprob},
+    Ollama,
+};
 
 use request::GenerationRequest;
 
@@ -10,11 +13,8 @@ pub mod request;
 #[cfg(feature = "stream")]
 /// A stream of `GenerationResponse` objects
 pub type GenerationResponseStream = std::pin::Pin<
-    Box<
-        dyn tokio_stream::Stream<Item = crate::error::Result<GenerationResponseStreamChunk>> + Send,
-    >,
+    Box<dyn tokio_stream::Stream<Item = crate::error::Result<GenerationResponse>> + Send>,
 >;
-pub type GenerationResponseStreamChunk = Vec<GenerationResponse>;
 
 impl Ollama {
     #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
@@ -25,43 +25,29 @@ impl Ollama {
         &self,
         request: GenerationRequest<'_>,
     ) -> crate::error::Result<GenerationResponseStream> {
-        use tokio_stream::StreamExt;
-
-        use crate::error::OllamaError;
-
         let mut request = request;
         request.stream = true;
+        request.options = self.request_options(request.options)?;
+        validate_top_logprobs(request.top_logprobs)?;
+        request.keep_alive = request
+            .keep_alive
+            .or_else(|| self.default_keep_alive.clone());
+
+        self.check_capabilities(&request.required_capabilities())
+            .await?;
+        self.validate_model_capabilities(
+            &request.model_name,
+            &request.required_model_capabilities(),
+        )
+        .await?;
 
         let url = format!("{}api/generate", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
-
-        let res = builder.body(serialized).send().await?;
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(
-                res.text().await.unwrap_or_else(|e| e.to_string()),
-            ));
-        }
+        let res = self.send_stream_request(builder, true).await?;
 
-        let stream = Box::new(res.bytes_stream().map(|res| match res {
-            Ok(bytes) => {
-                let res = serde_json::Deserializer::from_slice(&bytes).into_iter();
-                let res = res
-                    .filter_map(Result::ok) // Filter out the errors
-                    .collect::<Vec<GenerationResponse>>();
-                Ok(res)
-            }
-            Err(e) => Err(OllamaError::Other(format!(
-                "Failed to read response: {}",
-                e
-            ))),
-        }));
-
-        Ok(std::pin::Pin::from(stream))
+        Ok(self.ndjson_stream(res))
     }
 
     /// Completion generation with a single response.
@@ -72,24 +58,26 @@ impl Ollama {
     ) -> crate::error::Result<GenerationResponse> {
         let mut request = request;
         request.stream = false;
+        request.options = self.request_options(request.options)?;
+        validate_top_logprobs(request.top_logprobs)?;
+        request.keep_alive = request
+            .keep_alive
+            .or_else(|| self.default_keep_alive.clone());
+
+        self.check_capabilities(&request.required_capabilities())
+            .await?;
+        self.validate_model_capabilities(
+            &request.model_name,
+            &request.required_model_capabilities(),
+        )
+        .await?;
 
         let url = format!("{}api/generate", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        let res = builder.body(serialized).send().await?;
-
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(
-                res.text().await.unwrap_or_else(|e| e.to_string()),
-            ));
-        }
-
-        let res = res.bytes().await?;
-        let res = serde_json::from_slice::<GenerationResponse>(&res)?;
+        let bytes = self.send_request(builder, true).await?;
+        let res = serde_json::from_slice::<GenerationResponse>(&bytes)?;
 
         Ok(res)
     }
@@ -107,6 +95,12 @@ pub struct GenerationResponse {
     pub created_at: String,
     /// The response of the completion. This can be the entire completion or only a token if the completion is streaming.
     pub response: String,
+    /// The reasoning of the model before its response, when thinking is enabled. Like the response, this can be only a token if the completion is streaming.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<String>,
+    /// The log probabilities of the generated tokens, when requested. If the completion is streaming, these are only the ones of this response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<Vec<TokenLogprob>>,
     /// Whether the completion is done. If the completion is streaming, this will be false until the last response.
     pub done: bool,
     /// An encoding of the conversation used in this response, this can be sent in the next request to keep a conversational memory
@@ -122,3 +116,83 @@ pub struct GenerationResponse {
     /// Time spent in nanoseconds generating the response
     pub eval_duration: Option<u64>,
 }
+
+/// Accumulates the responses of a generation stream into the full completion, keeping the
+/// reasoning of the model separate from its response.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_rs::generation::completion::{request::GenerationRequest, GenerationResponseAccumulator};
+/// use ollama_rs::Ollama;
+/// use tokio_stream::StreamExt;
+///
+/// # async fn run() -> ollama_rs::error::Result<()> {
+/// let ollama = Ollama::default();
+/// let request = GenerationRequest::new("qwen3", "Why is the sky blue?").think(true);
+///
+/// let mut stream = ollama.generate_stream(request).await?;
+/// let mut accumulator = GenerationResponseAccumulator::new();
+/// while let Some(res) = stream.next().await {
+///     accumulator.push(&res?);
+/// }
+///
+/// println!("Thought: {}", accumulator.thinking());
+/// println!("Answer: {}", accumulator.response());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GenerationResponseAccumulator {
+    response: String,
+    thinking: String,
+    logprobs: Vec<TokenLogprob>,
+    context: Option<GenerationContext>,
+    done: bool,
+}
+
+impl GenerationResponseAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a response of the stream.
+    pub fn push(&mut self, response: &GenerationResponse) {
+        self.response.push_str(&response.response);
+        if let Some(thinking) = &response.thinking {
+            self.thinking.push_str(thinking);
+        }
+        if let Some(logprobs) = &response.logprobs {
+            self.logprobs.extend(logprobs.iter().cloned());
+        }
+        if let Some(context) = &response.context {
+            self.context = Some(context.clone());
+        }
+        self.done |= response.done;
+    }
+
+    /// The response received so far.
+    pub fn response(&self) -> &str {
+        &self.response
+    }
+
+    /// The reasoning received so far.
+    pub fn thinking(&self) -> &str {
+        &self.thinking
+    }
+
+    /// The log probabilities of the tokens received so far, when requested.
+    pub fn logprobs(&self) -> &[TokenLogprob] {
+        &self.logprobs
+    }
+
+    /// The encoding of the conversation, sent with the last response of the stream.
+    pub fn context(&self) -> Option<&GenerationContext> {
+        self.context.as_ref()
+    }
+
+    /// Whether the last response of the stream was received.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
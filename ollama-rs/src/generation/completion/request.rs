@@ -5,9 +5,10 @@ use serde::Serialize;
 use crate::{
     generation::{
         images::Image,
-        parameters::{FormatType, KeepAlive},
+        parameters::{FormatType, KeepAlive, Think},
     },
-    models::ModelOptions,
+    models::{ModelCapability, ModelOptions},
+    version::Capability,
 };
 
 use super::GenerationContext;
@@ -33,13 +34,21 @@ pub struct GenerationRequest<'a> {
     pub format: Option<FormatType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub keep_alive: Option<KeepAlive>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub think: Option<Think>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_logprobs: Option<u32>,
     pub(crate) stream: bool,
 }
 
 impl<'a> GenerationRequest<'a> {
-    pub fn new(model_name: String, prompt: impl Into<Cow<'a, str>>) -> Self {
+    pub fn new(model_name: impl Into<String>, prompt: impl Into<Cow<'a, str>>) -> Self {
         Self {
-            model_name,
+            model_name: model_name.into(),
             prompt: prompt.into(),
             suffix: None,
             images: Vec::new(),
@@ -49,13 +58,17 @@ impl<'a> GenerationRequest<'a> {
             context: None,
             format: None,
             keep_alive: None,
+            think: None,
+            raw: None,
+            logprobs: None,
+            top_logprobs: None,
             // Stream value will be overwritten by Ollama::generate_stream() and Ollama::generate() methods
             stream: false,
         }
     }
 
     /// Creates a new generation request with an suffix. Useful for code completion requests
-    pub fn new_with_suffix(model_name: String, prompt: String, suffix: String) -> Self {
+    pub fn new_with_suffix(model_name: impl Into<String>, prompt: String, suffix: String) -> Self {
         let out = Self::new(model_name, prompt);
         out.suffix(suffix)
     }
@@ -113,4 +126,59 @@ impl<'a> GenerationRequest<'a> {
         self.keep_alive = Some(keep_alive);
         self
     }
+
+    /// Whether a reasoning model thinks before answering. The reasoning is returned in the `thinking`
+    /// field of the responses, separately from the response.
+    pub fn think(mut self, think: impl Into<Think>) -> Self {
+        self.think = Some(think.into());
+        self
+    }
+
+    /// Send the prompt as is, without applying the template of the model. The prompt must then
+    /// contain the special tokens the model expects, and no context is returned.
+    pub fn raw(mut self, raw: bool) -> Self {
+        self.raw = Some(raw);
+        self
+    }
+
+    /// Return the log probability of each generated token, see [`crate::generation::logprobs::TokenLogprob`].
+    pub fn logprobs(mut self, logprobs: bool) -> Self {
+        self.logprobs = Some(logprobs);
+        self
+    }
+
+    /// Also return the `top_logprobs` most likely tokens at each position, at most
+    /// [`crate::generation::logprobs::MAX_TOP_LOGPROBS`]. This enables [`Self::logprobs`].
+    pub fn top_logprobs(mut self, top_logprobs: u32) -> Self {
+        self.logprobs = Some(true);
+        self.top_logprobs = Some(top_logprobs);
+        self
+    }
+
+    /// The capabilities the server needs to handle this request.
+    pub(crate) fn required_capabilities(&self) -> Vec<Capability> {
+        let mut capabilities = vec![];
+        if matches!(self.format, Some(FormatType::StructuredJson(_))) {
+            capabilities.push(Capability::StructuredOutputs);
+        }
+        if self.think.is_some() {
+            capabilities.push(Capability::Thinking);
+        }
+        capabilities
+    }
+
+    /// The capabilities the model needs to handle this request.
+    pub(crate) fn required_model_capabilities(&self) -> Vec<ModelCapability> {
+        let mut capabilities = vec![ModelCapability::Completion];
+        if !self.images.is_empty() {
+            capabilities.push(ModelCapability::Vision);
+        }
+        if self.suffix.is_some() {
+            capabilities.push(ModelCapability::Insert);
+        }
+        if self.think.is_some_and(|t| t.is_enabled()) {
+            capabilities.push(ModelCapability::Thinking);
+        }
+        capabilities
+    }
 }
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use crate::{error::OllamaError, Ollama};
+use crate::Ollama;
 
 use self::request::GenerateEmbeddingsRequest;
 
@@ -12,25 +12,25 @@ impl Ollama {
     /// * `prompt` - Prompt to generate embeddings for
     pub async fn generate_embeddings(
         &self,
-        request: GenerateEmbeddingsRequest,
+        mut request: GenerateEmbeddingsRequest,
     ) -> crate::error::Result<GenerateEmbeddingsResponse> {
-        let url = format!("{}api/embed", self.url_str());
-        let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
+        request.options = self.request_options(request.options)?;
+        request.keep_alive = request
+            .keep_alive
+            .or_else(|| self.default_keep_alive.clone());
 
-        let res = builder.body(serialized).send().await?;
+        self.validate_model_capabilities(
+            &request.model_name,
+            &request.required_model_capabilities(),
+        )
+        .await?;
 
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(
-                res.text().await.unwrap_or_else(|e| e.to_string()),
-            ));
-        }
+        let url = format!("{}api/embed", self.url_str());
+        let serialized = serde_json::to_string(&request)?;
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        let res = res.bytes().await?;
-        let res = serde_json::from_slice::<GenerateEmbeddingsResponse>(&res)?;
+        let bytes = self.send_request(builder, true).await?;
+        let res = serde_json::from_slice::<GenerateEmbeddingsResponse>(&bytes)?;
 
         Ok(res)
     }
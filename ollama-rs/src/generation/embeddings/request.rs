@@ -1,6 +1,9 @@
 use serde::{Serialize, Serializer};
 
-use crate::{generation::parameters::KeepAlive, models::ModelOptions};
+use crate::{
+    generation::parameters::KeepAlive,
+    models::{ModelCapability, ModelOptions},
+};
 
 #[derive(Debug)]
 pub enum EmbeddingsInput {
@@ -51,20 +54,20 @@ impl Serialize for EmbeddingsInput {
 #[derive(Debug, Serialize, Default)]
 pub struct GenerateEmbeddingsRequest {
     #[serde(rename = "model")]
-    model_name: String,
+    pub(crate) model_name: String,
     input: EmbeddingsInput,
     #[serde(skip_serializing_if = "Option::is_none")]
     truncate: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    options: Option<ModelOptions>,
+    pub(crate) options: Option<ModelOptions>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    keep_alive: Option<KeepAlive>,
+    pub(crate) keep_alive: Option<KeepAlive>,
 }
 
 impl GenerateEmbeddingsRequest {
-    pub fn new(model_name: String, input: EmbeddingsInput) -> Self {
+    pub fn new(model_name: impl Into<String>, input: EmbeddingsInput) -> Self {
         Self {
-            model_name,
+            model_name: model_name.into(),
             input,
             ..Default::default()
         }
@@ -84,4 +87,9 @@ impl GenerateEmbeddingsRequest {
         self.truncate = Some(truncate);
         self
     }
+
+    /// The capabilities the model needs to handle this request.
+    pub(crate) fn required_model_capabilities(&self) -> Vec<ModelCapability> {
+        vec![ModelCapability::Embedding]
+    }
 }
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::OllamaError;
+
+/// How many of the most likely tokens can be returned at each position, see `top_logprobs`.
+pub const MAX_TOP_LOGPROBS: u32 = 20;
+
+/// The log probability of a generated token, returned when log probabilities are requested.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenLogprob {
+    /// The text of the token.
+    pub token: String,
+    /// The natural logarithm of the probability of the token.
+    pub logprob: f64,
+    /// The raw bytes of the token, which may not be valid UTF-8 on their own.
+    #[serde(default)]
+    pub bytes: Option<Vec<u8>>,
+    /// The most likely tokens at this position, including this one, most likely first.
+    /// Only returned when `top_logprobs` is set.
+    #[serde(default)]
+    pub top_logprobs: Vec<TokenLogprob>,
+}
+
+impl TokenLogprob {
+    /// The probability of the token, between 0 and 1.
+    pub fn probability(&self) -> f64 {
+        self.logprob.exp()
+    }
+}
+
+/// Fails if more of the most likely tokens are requested than the server returns.
+pub(crate) fn validate_top_logprobs(top_logprobs: Option<u32>) -> crate::error::Result<()> {
+    match top_logprobs {
+        Some(top_logprobs) if top_logprobs > MAX_TOP_LOGPROBS => {
+            Err(OllamaError::InvalidModelOption {
+                option: "top_logprobs",
+                reason: format!("must be at most {}", MAX_TOP_LOGPROBS),
+            })
+        }
+        _ => Ok(()),
+    }
+}
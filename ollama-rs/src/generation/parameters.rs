@@ -1,9 +1,10 @@
 use schemars::{gen::SchemaSettings, schema::RootSchema};
 pub use schemars::{schema_for, JsonSchema};
-use serde::{Serialize, Deserialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 
 /// The format to return a response in
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum FormatType {
     Json,
 
@@ -63,6 +64,78 @@ impl JsonStructure {
     }
 }
 
+/// Whether a reasoning model thinks before answering, and how much.
+///
+/// The levels are only supported by some models, such as `gpt-oss`, while the others only support
+/// enabling or disabling thinking. Requires Ollama 0.9.0 or greater.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Think {
+    Enabled,
+    Disabled,
+    Low,
+    Medium,
+    High,
+}
+
+impl Think {
+    /// Whether the model thinks at all.
+    pub fn is_enabled(&self) -> bool {
+        *self != Think::Disabled
+    }
+}
+
+impl From<bool> for Think {
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            Think::Enabled
+        } else {
+            Think::Disabled
+        }
+    }
+}
+
+impl Serialize for Think {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Think::Enabled => serializer.serialize_bool(true),
+            Think::Disabled => serializer.serialize_bool(false),
+            Think::Low => serializer.serialize_str("low"),
+            Think::Medium => serializer.serialize_str("medium"),
+            Think::High => serializer.serialize_str("high"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Think {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Bool(bool),
+            Level(String),
+        }
+
+        match Value::deserialize(deserializer)? {
+            Value::Bool(enabled) => Ok(enabled.into()),
+            Value::Level(level) => match level.as_str() {
+                "low" => Ok(Think::Low),
+                "medium" => Ok(Think::Medium),
+                "high" => Ok(Think::High),
+                _ => Err(serde::de::Error::custom(format!(
+                    "unknown think level {:?}",
+                    level
+                ))),
+            },
+        }
+    }
+}
+
 /// Used to control how long a model stays loaded in memory, by default models are unloaded after 5 minutes of inactivity
 #[derive(Debug, Clone)]
 pub enum KeepAlive {
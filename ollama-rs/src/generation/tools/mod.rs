@@ -129,3 +129,15 @@ pub struct ToolCallFunction {
     // But fixing it would be a big effort
     arguments: Value,
 }
+
+impl ToolCallFunction {
+    /// The name of the called tool.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The arguments of the call, which should follow the parameters of the tool.
+    pub fn arguments(&self) -> &Value {
+        &self.arguments
+    }
+}
@@ -1,10 +1,26 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
 use url::Url;
 
+use crate::{
+    cancellation::CancellationToken,
+    generation::parameters::KeepAlive,
+    models::{ModelCapability, ModelName, ModelOptions},
+    retry::RetryPolicy,
+    version::OllamaVersion,
+};
+
 #[cfg(feature = "macros")]
 pub use ollama_rs_macros::{function, tool_group};
 
+pub mod builder;
+pub mod cancellation;
 pub mod coordinator;
 pub mod error;
 pub mod generation;
@@ -13,6 +29,10 @@ pub mod generation;
 pub mod headers;
 pub mod history;
 pub mod models;
+pub mod retry;
+#[cfg(feature = "stream")]
+mod stream;
+pub mod version;
 
 /// A trait to try to convert some type into a [`Url`].
 ///
@@ -86,6 +106,19 @@ pub struct Ollama {
     pub(crate) reqwest_client: reqwest::Client,
     #[cfg(feature = "headers")]
     pub(crate) request_headers: reqwest::header::HeaderMap,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) request_timeout: Option<Duration>,
+    #[cfg_attr(not(feature = "stream"), allow(dead_code))]
+    pub(crate) stream_idle_timeout: Option<Duration>,
+    pub(crate) default_options: Option<ModelOptions>,
+    pub(crate) default_keep_alive: Option<KeepAlive>,
+    pub(crate) cancellation: Option<CancellationToken>,
+    pub(crate) commit_partial_on_cancel: bool,
+    pub(crate) keep_thinking_in_history: bool,
+    pub(crate) check_capabilities: bool,
+    pub(crate) server_version: Arc<OnceLock<OllamaVersion>>,
+    pub(crate) validate_model_capabilities: bool,
+    pub(crate) model_capabilities: Arc<Mutex<HashMap<ModelName, Vec<ModelCapability>>>>,
 }
 
 /// The main struct representing an Ollama client.
@@ -97,6 +130,20 @@ pub struct Ollama {
 /// * `url` - The base URL of the Ollama service.
 /// * `reqwest_client` - The HTTP client used for requests.
 /// * `request_headers` - Optional headers for requests (enabled with the `headers` feature).
+/// * `retry_policy` - How failed requests are retried.
+/// * `request_timeout` - Timeout for requests whose response is not streamed.
+/// * `stream_idle_timeout` - Maximum time to wait for the next chunk of a streamed response.
+/// * `default_options` - Model options used by requests that do not set any.
+/// * `default_keep_alive` - Keep alive used by requests that do not set any.
+/// * `cancellation` - A token aborting every request when cancelled, see [`Ollama::with_cancellation`].
+/// * `commit_partial_on_cancel` - Whether cancelled chat streams with history keep the partial answer.
+/// * `keep_thinking_in_history` - Whether the reasoning of the model is kept in the chat history.
+/// * `check_capabilities` - Whether requests fail fast when the server is too old for the features they use.
+/// * `server_version` - The version of the server, cached by capability checks.
+/// * `validate_model_capabilities` - Whether requests fail fast when the model cannot serve them.
+/// * `model_capabilities` - The capabilities of each model, cached by model capability checks.
+///
+/// Use [`Ollama::builder`] to configure all of these at once.
 impl Ollama {
     /// Creates a new `Ollama` instance with the specified host and port.
     ///
@@ -143,6 +190,18 @@ impl Ollama {
             reqwest_client,
             #[cfg(feature = "headers")]
             request_headers: reqwest::header::HeaderMap::new(),
+            retry_policy: RetryPolicy::default(),
+            request_timeout: None,
+            stream_idle_timeout: None,
+            default_options: None,
+            default_keep_alive: None,
+            cancellation: None,
+            commit_partial_on_cancel: false,
+            keep_thinking_in_history: false,
+            check_capabilities: false,
+            server_version: Arc::default(),
+            validate_model_capabilities: false,
+            model_capabilities: Arc::default(),
         }
     }
 
@@ -217,6 +276,18 @@ impl Default for Ollama {
             reqwest_client: reqwest::Client::new(),
             #[cfg(feature = "headers")]
             request_headers: reqwest::header::HeaderMap::new(),
+            retry_policy: RetryPolicy::default(),
+            request_timeout: None,
+            stream_idle_timeout: None,
+            default_options: None,
+            default_keep_alive: None,
+            cancellation: None,
+            commit_partial_on_cancel: false,
+            keep_thinking_in_history: false,
+            check_capabilities: false,
+            server_version: Arc::default(),
+            validate_model_capabilities: false,
+            model_capabilities: Arc::default(),
         }
     }
 }
@@ -1,11 +1,24 @@
 /// Modules related to model operations.
 ///
-/// These modules provide functionality for copying, creating, deleting,
-/// listing, pulling, pushing, and showing information about models.
+/// These modules provide functionality for uploading blobs, copying, creating, deleting,
+/// listing, pulling, pushing, and showing information about models, checking their capabilities,
+/// converting their parameters to and from options,
+/// tracking the progress of these operations, as well as loading, unloading
+/// and listing the models currently in memory.
+pub mod blobs;
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+#[cfg(feature = "stream")]
+pub mod bulk_pull;
+pub mod capabilities;
 pub mod copy;
 pub mod create;
 pub mod delete;
 pub mod list_local;
+pub mod list_running;
+pub mod load;
+pub mod name;
+pub mod parameters;
+pub mod progress;
 pub mod pull;
 pub mod push;
 pub mod show_info;
@@ -18,22 +31,74 @@ use serde_with;
 
 use serde::{Deserialize, Serialize};
 
+use crate::{error::OllamaError, Ollama};
+
+pub use name::ModelName;
+
 /// Represents a local model pulled from Ollama.
 ///
 /// This struct contains information about a model that has been pulled
-/// from the Ollama service, including its name, modification date, and size.
+/// from the Ollama service, including its name, modification date, size and details.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocalModel {
     pub name: String,
+    #[serde(default = "String::new")]
+    pub model: String,
     pub modified_at: String,
+    /// The size of the model on disk, in bytes.
     pub size: u64,
+    #[serde(default = "String::new")]
+    pub digest: String,
+    #[serde(default)]
+    pub details: ModelDetails,
+}
+
+/// Represents a model currently loaded in memory by Ollama.
+///
+/// This struct contains information about a running model, including its
+/// memory usage and when it will be unloaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningModel {
+    pub name: String,
+    #[serde(default = "String::new")]
+    pub model: String,
+    /// The total size of the model in memory, in bytes.
+    pub size: u64,
+    pub digest: String,
+    #[serde(default)]
+    pub details: ModelDetails,
+    /// When the model will be unloaded, in such format: `2024-06-04T14:38:31.83753-07:00`.
+    pub expires_at: String,
+    /// The part of the size of the model loaded in VRAM, in bytes.
+    #[serde(default)]
+    pub size_vram: u64,
+    /// The context length the model was loaded with. Only returned by recent versions of Ollama.
+    #[serde(default)]
+    pub context_length: Option<u64>,
+}
+
+/// Represents the details of a model, such as its format, family and quantization.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelDetails {
+    #[serde(default = "String::new")]
+    pub parent_model: String,
+    #[serde(default = "String::new")]
+    pub format: String,
+    #[serde(default = "String::new")]
+    pub family: String,
+    #[serde(default)]
+    pub families: Option<Vec<String>>,
+    #[serde(default = "String::new")]
+    pub parameter_size: String,
+    #[serde(default = "String::new")]
+    pub quantization_level: String,
 }
 
 /// Represents information about a model.
 ///
 /// This struct contains various fields that describe a model's attributes,
-/// such as its license, file, parameters, and template.
-/// Some fields may be empty if the model does not have them.
+/// such as its license, file, parameters, template, details and capabilities.
+/// Some fields may be empty if the model does not have them, or if the server is too old to send them.
 ///
 /// By default the modelfile is a string, but if the `modelfile` feature is enabled,
 /// it will be a `Modelfile` struct. See the modelfile crate for more information.
@@ -52,8 +117,143 @@ pub struct ModelInfo {
     pub parameters: String,
     #[serde(default = "String::new")]
     pub template: String,
+    #[serde(default = "String::new")]
+    pub system: String,
+    #[serde(default)]
+    pub details: ModelDetails,
+    /// The metadata of the model, such as `general.architecture` or `llama.context_length`.
+    /// See the typed accessors, such as [`ModelInfo::context_length`].
     #[serde(default = "serde_json::Map::new")]
     pub model_info: serde_json::Map<String, serde_json::Value>,
+    /// The metadata of the multimodal projector, for models supporting images.
+    #[serde(default = "serde_json::Map::new")]
+    pub projector_info: serde_json::Map<String, serde_json::Value>,
+    /// The tensors of the model. Only returned for verbose requests, see [`show_info::ShowModelInfoRequest::verbose`].
+    #[serde(default)]
+    pub tensors: Vec<TensorInfo>,
+    #[serde(default)]
+    pub capabilities: Vec<ModelCapability>,
+    #[serde(default = "String::new")]
+    pub modified_at: String,
+}
+
+impl ModelInfo {
+    /// Whether the model has the given capability.
+    pub fn has_capability(&self, capability: &ModelCapability) -> bool {
+        self.capabilities.contains(capability)
+    }
+
+    /// The architecture of the model, such as `llama`.
+    pub fn architecture(&self) -> Option<&str> {
+        self.model_info.get("general.architecture")?.as_str()
+    }
+
+    /// The number of parameters of the model.
+    pub fn parameter_count(&self) -> Option<u64> {
+        self.model_info.get("general.parameter_count")?.as_u64()
+    }
+
+    /// The maximum context length the model was trained with, in tokens.
+    pub fn context_length(&self) -> Option<u64> {
+        self.architecture_value("context_length")?.as_u64()
+    }
+
+    /// The size of the embeddings of the model.
+    pub fn embedding_length(&self) -> Option<u64> {
+        self.architecture_value("embedding_length")?.as_u64()
+    }
+
+    /// The number of layers of the model.
+    pub fn block_count(&self) -> Option<u64> {
+        self.architecture_value("block_count")?.as_u64()
+    }
+
+    /// The number of attention heads of the model.
+    pub fn head_count(&self) -> Option<u64> {
+        self.architecture_value("attention.head_count")?.as_u64()
+    }
+
+    /// The tokenizer of the model, such as `gpt2` or `llama`.
+    pub fn tokenizer_model(&self) -> Option<&str> {
+        self.model_info.get("tokenizer.ggml.model")?.as_str()
+    }
+
+    /// The tokens of the vocabulary of the model. Only returned for verbose requests.
+    pub fn tokens(&self) -> Option<Vec<&str>> {
+        self.model_info
+            .get("tokenizer.ggml.tokens")?
+            .as_array()?
+            .iter()
+            .map(|t| t.as_str())
+            .collect()
+    }
+
+    /// The size of the vocabulary of the model.
+    pub fn vocab_size(&self) -> Option<u64> {
+        self.architecture_value("vocab_size")
+            .and_then(|v| v.as_u64())
+            .or_else(|| {
+                let tokens = self.model_info.get("tokenizer.ggml.tokens")?.as_array()?;
+                Some(tokens.len() as u64)
+            })
+    }
+
+    /// The chat template embedded in the weights, in the Jinja format.
+    /// Ollama uses [`ModelInfo::template`] instead.
+    pub fn chat_template(&self) -> Option<&str> {
+        self.model_info.get("tokenizer.chat_template")?.as_str()
+    }
+
+    /// Looks up a key of `model_info` prefixed with the architecture, such as `llama.context_length`.
+    pub fn architecture_value(&self, key: &str) -> Option<&serde_json::Value> {
+        let architecture = self.architecture()?;
+        self.model_info.get(&format!("{}.{}", architecture, key))
+    }
+}
+
+/// A tensor of a model, as listed by a verbose [`crate::Ollama::show_model_info`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TensorInfo {
+    pub name: String,
+    /// The data type of the tensor, such as `F16` or `Q4_K`.
+    #[serde(rename = "type")]
+    pub tensor_type: String,
+    pub shape: Vec<u64>,
+}
+
+/// A capability of a model, as reported by [`crate::Ollama::show_model_info`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelCapability {
+    /// The model can generate text.
+    Completion,
+    /// The model can call tools.
+    Tools,
+    /// The model accepts images.
+    Vision,
+    /// The model can generate embeddings.
+    Embedding,
+    /// The model can fill in the middle, given a suffix.
+    Insert,
+    /// The model can reason before answering.
+    Thinking,
+    /// A capability not known to this version of the crate.
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl std::fmt::Display for ModelCapability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelCapability::Completion => f.write_str("completion"),
+            ModelCapability::Tools => f.write_str("tools"),
+            ModelCapability::Vision => f.write_str("vision"),
+            ModelCapability::Embedding => f.write_str("embedding"),
+            ModelCapability::Insert => f.write_str("insert"),
+            ModelCapability::Thinking => f.write_str("thinking"),
+            ModelCapability::Other(capability) => f.write_str(capability),
+        }
+    }
 }
 
 // Options for generation requests to Ollama.
@@ -107,6 +307,45 @@ pub struct ModelOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub(super) top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) num_keep: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) typical_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) min_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) penalize_newline: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) numa: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) num_batch: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) main_gpu: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) use_mmap: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) use_mlock: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) low_vram: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub(super) vocab_only: Option<bool>,
 }
 
 impl ModelOptions {
@@ -205,4 +444,346 @@ impl ModelOptions {
         self.top_p = Some(top_p);
         self
     }
+
+    /// Number of tokens to keep from the initial prompt when the context window is shifted. (Default: 4, -1 = all)
+    pub fn num_keep(mut self, num_keep: i32) -> Self {
+        self.num_keep = Some(num_keep);
+        self
+    }
+
+    /// Locally typical sampling, keeping the tokens whose probability is close to the expected one. A lower value will generate more focused text. (Default: 1.0, 1.0 = disabled)
+    pub fn typical_p(mut self, typical_p: f32) -> Self {
+        self.typical_p = Some(typical_p);
+        self
+    }
+
+    /// Alternative to the top_p, and aims to ensure a balance of quality and variety. The parameter p represents the minimum probability for a token to be considered, relative to the probability of the most likely token. For example, with p=0.05 and the most likely token having a probability of 0.9, logits with a value less than 0.045 are filtered out. (Default: 0.0)
+    pub fn min_p(mut self, min_p: f32) -> Self {
+        self.min_p = Some(min_p);
+        self
+    }
+
+    /// Penalizes the tokens that already appeared in the text, however often, making the model more likely to talk about new topics. (Default: 0.0)
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+        self.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    /// Penalizes the tokens in proportion to how often they already appeared in the text, making the model less likely to repeat the same lines. (Default: 0.0)
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+        self.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    /// Whether the newline token is penalized along with the repetitions. (Default: true)
+    pub fn penalize_newline(mut self, penalize_newline: bool) -> Self {
+        self.penalize_newline = Some(penalize_newline);
+        self
+    }
+
+    /// Enable NUMA support. (Default: false)
+    pub fn numa(mut self, numa: bool) -> Self {
+        self.numa = Some(numa);
+        self
+    }
+
+    /// The number of prompt tokens processed at once. A higher value speeds up the processing of long prompts, at the cost of memory. (Default: 512)
+    pub fn num_batch(mut self, num_batch: u32) -> Self {
+        self.num_batch = Some(num_batch);
+        self
+    }
+
+    /// The GPU used for small tensors when the model is split across several GPUs. (Default: 0)
+    pub fn main_gpu(mut self, main_gpu: u32) -> Self {
+        self.main_gpu = Some(main_gpu);
+        self
+    }
+
+    /// Map the model file in memory instead of reading it, loading only the needed parts. Disabling it can help if the model is larger than the available memory. (Default: true)
+    pub fn use_mmap(mut self, use_mmap: bool) -> Self {
+        self.use_mmap = Some(use_mmap);
+        self
+    }
+
+    /// Lock the model in memory, preventing it from being swapped out. (Default: false)
+    pub fn use_mlock(mut self, use_mlock: bool) -> Self {
+        self.use_mlock = Some(use_mlock);
+        self
+    }
+
+    /// Reduce the VRAM usage, at the cost of speed. (Default: false)
+    pub fn low_vram(mut self, low_vram: bool) -> Self {
+        self.low_vram = Some(low_vram);
+        self
+    }
+
+    /// Only load the vocabulary, not the weights. (Default: false)
+    pub fn vocab_only(mut self, vocab_only: bool) -> Self {
+        self.vocab_only = Some(vocab_only);
+        self
+    }
+
+    pub fn get_mirostat(&self) -> Option<u8> {
+        self.mirostat
+    }
+
+    pub fn get_mirostat_eta(&self) -> Option<f32> {
+        self.mirostat_eta
+    }
+
+    pub fn get_mirostat_tau(&self) -> Option<f32> {
+        self.mirostat_tau
+    }
+
+    pub fn get_num_ctx(&self) -> Option<u64> {
+        self.num_ctx
+    }
+
+    pub fn get_num_gqa(&self) -> Option<u32> {
+        self.num_gqa
+    }
+
+    pub fn get_num_gpu(&self) -> Option<u32> {
+        self.num_gpu
+    }
+
+    pub fn get_num_thread(&self) -> Option<u32> {
+        self.num_thread
+    }
+
+    pub fn get_repeat_last_n(&self) -> Option<i32> {
+        self.repeat_last_n
+    }
+
+    pub fn get_repeat_penalty(&self) -> Option<f32> {
+        self.repeat_penalty
+    }
+
+    pub fn get_temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
+    pub fn get_seed(&self) -> Option<i32> {
+        self.seed
+    }
+
+    pub fn get_stop(&self) -> Option<&[String]> {
+        self.stop.as_deref()
+    }
+
+    pub fn get_tfs_z(&self) -> Option<f32> {
+        self.tfs_z
+    }
+
+    pub fn get_num_predict(&self) -> Option<i32> {
+        self.num_predict
+    }
+
+    pub fn get_top_k(&self) -> Option<u32> {
+        self.top_k
+    }
+
+    pub fn get_top_p(&self) -> Option<f32> {
+        self.top_p
+    }
+
+    pub fn get_num_keep(&self) -> Option<i32> {
+        self.num_keep
+    }
+
+    pub fn get_typical_p(&self) -> Option<f32> {
+        self.typical_p
+    }
+
+    pub fn get_min_p(&self) -> Option<f32> {
+        self.min_p
+    }
+
+    pub fn get_presence_penalty(&self) -> Option<f32> {
+        self.presence_penalty
+    }
+
+    pub fn get_frequency_penalty(&self) -> Option<f32> {
+        self.frequency_penalty
+    }
+
+    pub fn get_penalize_newline(&self) -> Option<bool> {
+        self.penalize_newline
+    }
+
+    pub fn get_numa(&self) -> Option<bool> {
+        self.numa
+    }
+
+    pub fn get_num_batch(&self) -> Option<u32> {
+        self.num_batch
+    }
+
+    pub fn get_main_gpu(&self) -> Option<u32> {
+        self.main_gpu
+    }
+
+    pub fn get_use_mmap(&self) -> Option<bool> {
+        self.use_mmap
+    }
+
+    pub fn get_use_mlock(&self) -> Option<bool> {
+        self.use_mlock
+    }
+
+    pub fn get_low_vram(&self) -> Option<bool> {
+        self.low_vram
+    }
+
+    pub fn get_vocab_only(&self) -> Option<bool> {
+        self.vocab_only
+    }
+
+    /// Layers `other` over these options: the options set in `other` take precedence,
+    /// the others keep their value from `self`.
+    ///
+    /// This is how the options of a request are combined with [`crate::Ollama`]'s default options.
+    pub fn merge(self, other: ModelOptions) -> Self {
+        Self {
+            mirostat: other.mirostat.or(self.mirostat),
+            mirostat_eta: other.mirostat_eta.or(self.mirostat_eta),
+            mirostat_tau: other.mirostat_tau.or(self.mirostat_tau),
+            num_ctx: other.num_ctx.or(self.num_ctx),
+            num_gqa: other.num_gqa.or(self.num_gqa),
+            num_gpu: other.num_gpu.or(self.num_gpu),
+            num_thread: other.num_thread.or(self.num_thread),
+            repeat_last_n: other.repeat_last_n.or(self.repeat_last_n),
+            repeat_penalty: other.repeat_penalty.or(self.repeat_penalty),
+            temperature: other.temperature.or(self.temperature),
+            seed: other.seed.or(self.seed),
+            stop: other.stop.or(self.stop),
+            tfs_z: other.tfs_z.or(self.tfs_z),
+            num_predict: other.num_predict.or(self.num_predict),
+            top_k: other.top_k.or(self.top_k),
+            top_p: other.top_p.or(self.top_p),
+            num_keep: other.num_keep.or(self.num_keep),
+            typical_p: other.typical_p.or(self.typical_p),
+            min_p: other.min_p.or(self.min_p),
+            presence_penalty: other.presence_penalty.or(self.presence_penalty),
+            frequency_penalty: other.frequency_penalty.or(self.frequency_penalty),
+            penalize_newline: other.penalize_newline.or(self.penalize_newline),
+            numa: other.numa.or(self.numa),
+            num_batch: other.num_batch.or(self.num_batch),
+            main_gpu: other.main_gpu.or(self.main_gpu),
+            use_mmap: other.use_mmap.or(self.use_mmap),
+            use_mlock: other.use_mlock.or(self.use_mlock),
+            low_vram: other.low_vram.or(self.low_vram),
+            vocab_only: other.vocab_only.or(self.vocab_only),
+        }
+    }
+
+    /// Checks that the options are in the range accepted by Ollama.
+    ///
+    /// This is done before sending requests, so that invalid values are reported instead of
+    /// being silently clamped or ignored by the server.
+    pub fn validate(&self) -> crate::error::Result<()> {
+        fn check<T: Copy>(
+            option: &'static str,
+            value: Option<T>,
+            valid: impl Fn(T) -> bool,
+            expected: &str,
+        ) -> crate::error::Result<()> {
+            match value {
+                Some(value) if !valid(value) => Err(OllamaError::InvalidModelOption {
+                    option,
+                    reason: format!("must be {}", expected),
+                }),
+                _ => Ok(()),
+            }
+        }
+
+        let non_negative = |v: f32| v.is_finite() && v >= 0.0;
+        let probability = |v: f32| (0.0..=1.0).contains(&v);
+
+        check("mirostat", self.mirostat, |v| v <= 2, "0, 1 or 2")?;
+        check(
+            "mirostat_eta",
+            self.mirostat_eta,
+            non_negative,
+            "0 or greater",
+        )?;
+        check(
+            "mirostat_tau",
+            self.mirostat_tau,
+            non_negative,
+            "0 or greater",
+        )?;
+        check("num_ctx", self.num_ctx, |v| v > 0, "greater than 0")?;
+        check("num_keep", self.num_keep, |v| v >= -1, "-1 or greater")?;
+        check("num_batch", self.num_batch, |v| v > 0, "greater than 0")?;
+        check(
+            "repeat_last_n",
+            self.repeat_last_n,
+            |v| v >= -1,
+            "-1 or greater",
+        )?;
+        check(
+            "repeat_penalty",
+            self.repeat_penalty,
+            non_negative,
+            "0 or greater",
+        )?;
+        check(
+            "temperature",
+            self.temperature,
+            non_negative,
+            "0 or greater",
+        )?;
+        check("tfs_z", self.tfs_z, non_negative, "0 or greater")?;
+        check(
+            "num_predict",
+            self.num_predict,
+            |v| v >= -2,
+            "-2 or greater",
+        )?;
+        check("top_p", self.top_p, probability, "between 0 and 1")?;
+        check("min_p", self.min_p, probability, "between 0 and 1")?;
+        check("typical_p", self.typical_p, probability, "between 0 and 1")?;
+        check(
+            "presence_penalty",
+            self.presence_penalty,
+            f32::is_finite,
+            "a finite number",
+        )?;
+        check(
+            "frequency_penalty",
+            self.frequency_penalty,
+            f32::is_finite,
+            "a finite number",
+        )?;
+
+        Ok(())
+    }
+}
+
+impl Ollama {
+    /// Layers the options of a request over the default options, and validates the result.
+    pub(crate) fn request_options(
+        &self,
+        options: Option<ModelOptions>,
+    ) -> crate::error::Result<Option<ModelOptions>> {
+        let options = match (self.default_options.clone(), options) {
+            (Some(defaults), Some(options)) => Some(defaults.merge(options)),
+            (defaults, options) => options.or(defaults),
+        };
+
+        if let Some(options) = &options {
+            options.validate()?;
+        }
+
+        Ok(options)
+    }
+}
+
+/// Whether two model names refer to the same model, `llama2` being the same as `llama2:latest`.
+pub(crate) fn is_same_model(a: &str, b: &str) -> bool {
+    match (ModelName::parse(a), ModelName::parse(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
 }
@@ -0,0 +1,90 @@
+use reqwest::StatusCode;
+
+use crate::{error::OllamaError, Ollama};
+
+#[cfg(feature = "stream")]
+use tokio::io::{AsyncRead, AsyncSeek};
+
+impl Ollama {
+    /// Check whether a blob is present on the server.
+    /// - `digest` - The SHA256 digest of the blob, such as `sha256:29fdb92e57cf0827ded04ae6461b5931d01fa595843f55d36f5b275a52087dd2`.
+    pub async fn blob_exists(&self, digest: &str) -> crate::error::Result<bool> {
+        let url = format!("{}api/blobs/{}", self.url_str(), digest);
+        let builder = self.reqwest_client.head(url);
+
+        match self.send_request(builder, true).await {
+            Ok(_) => Ok(true),
+            Err(OllamaError::ApiError(e)) if e.status == StatusCode::NOT_FOUND => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+    #[cfg(feature = "stream")]
+    /// Upload a file, such as GGUF or safetensors weights, as a blob to use when creating a model.
+    /// Returns the digest of the blob, to be used in [`super::create::CreateModelRequest`].
+    ///
+    /// See [`Ollama::push_blob_reader`].
+    /// - `path` - The path of the file to upload.
+    pub async fn push_blob(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> crate::error::Result<String> {
+        let file = tokio::fs::File::open(path).await?;
+
+        self.push_blob_reader(file).await
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+    #[cfg(feature = "stream")]
+    /// Upload the content of a reader as a blob to use when creating a model.
+    /// Returns the digest of the blob, to be used in [`super::create::CreateModelRequest`].
+    ///
+    /// The digest is part of the upload URL, so the content is read twice: once to compute
+    /// its SHA256 digest, then again from the start to stream it to the server.
+    /// Nothing is uploaded if the server already has the blob.
+    /// - `reader` - The content to upload.
+    pub async fn push_blob_reader<R>(&self, mut reader: R) -> crate::error::Result<String>
+    where
+        R: AsyncRead + AsyncSeek + Send + Unpin + 'static,
+    {
+        use tokio::io::AsyncSeekExt;
+
+        let digest = self.cancellable(sha256_digest(&mut reader)).await?;
+
+        if self.blob_exists(&digest).await? {
+            return Ok(digest);
+        }
+
+        reader.rewind().await?;
+
+        let url = format!("{}api/blobs/{}", self.url_str(), digest);
+        let body = reqwest::Body::wrap_stream(tokio_util::io::ReaderStream::new(reader));
+        let builder = self.reqwest_client.post(url).body(body);
+
+        // The request timeout would abort the upload of large files, so the upload is only
+        // bounded by the connection timeout and cancellation, as streamed requests are
+        self.send_stream_request(builder, true).await?;
+
+        Ok(digest)
+    }
+}
+
+/// Computes the digest of the content of `reader`, as expected by the blobs API.
+#[cfg(feature = "stream")]
+async fn sha256_digest<R: AsyncRead + Unpin>(reader: &mut R) -> crate::error::Result<String> {
+    use sha2::{Digest, Sha256};
+    use tokio::io::AsyncReadExt;
+
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
@@ -0,0 +1,357 @@
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use tokio::{
+    sync::{mpsc, Semaphore},
+    task::JoinSet,
+};
+use tokio_stream::{wrappers::UnboundedReceiverStream, Stream, StreamExt};
+
+use crate::{error::OllamaError, Ollama};
+
+use super::{
+    is_same_model,
+    progress::{ModelProgress, ProgressTracker},
+    LocalModel,
+};
+
+/// A stream of `BulkPullEvent` objects.
+pub type BulkPullEventStream =
+    std::pin::Pin<Box<dyn tokio_stream::Stream<Item = BulkPullEvent> + Send>>;
+
+/// The events of the pulls, which aborts them once dropped.
+struct BulkPullEvents {
+    events: UnboundedReceiverStream<BulkPullEvent>,
+    // Aborts every task when dropped
+    _tasks: JoinSet<()>,
+}
+
+impl Stream for BulkPullEvents {
+    type Item = BulkPullEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.events).poll_next(cx)
+    }
+}
+
+/// A model to pull as part of a [`BulkPullRequest`].
+#[derive(Debug, Clone)]
+pub struct PullTarget {
+    pub name: String,
+    /// The expected digest of the model. When set, the model is only skipped if the local
+    /// one has this digest; otherwise it is skipped as soon as it is present locally.
+    pub digest: Option<String>,
+}
+
+impl PullTarget {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            digest: None,
+        }
+    }
+
+    pub fn digest(mut self, digest: impl Into<String>) -> Self {
+        self.digest = Some(digest.into());
+        self
+    }
+
+    /// Whether `local` already is this model.
+    fn is_satisfied_by(&self, local: &LocalModel) -> bool {
+        if !is_same_model(&local.name, &self.name) {
+            return false;
+        }
+
+        match &self.digest {
+            Some(digest) => trim_digest(digest) == trim_digest(&local.digest),
+            None => true,
+        }
+    }
+}
+
+impl From<&str> for PullTarget {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for PullTarget {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+/// A request to pull several models at once.
+#[derive(Debug, Clone)]
+pub struct BulkPullRequest {
+    models: Vec<PullTarget>,
+    concurrency: usize,
+    max_retries: u32,
+    retry_delay: Duration,
+    allow_insecure: bool,
+    skip_existing: bool,
+}
+
+impl BulkPullRequest {
+    pub fn new<T: Into<PullTarget>>(models: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            models: models.into_iter().map(Into::into).collect(),
+            concurrency: 2,
+            max_retries: 3,
+            retry_delay: Duration::from_secs(1),
+            allow_insecure: false,
+            skip_existing: true,
+        }
+    }
+
+    /// How many models are pulled at the same time. (Default: 2)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// How many times an interrupted pull is restarted. The server resumes partially downloaded layers. (Default: 3)
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// How long to wait before restarting an interrupted pull. (Default: 1 second)
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Allow insecure connections to the library. Only use this if you are pulling from your own library during development.
+    pub fn allow_insecure(mut self, allow_insecure: bool) -> Self {
+        self.allow_insecure = allow_insecure;
+        self
+    }
+
+    /// Whether models already present locally are skipped, see [`PullTarget::digest`]. (Default: true)
+    pub fn skip_existing(mut self, skip_existing: bool) -> Self {
+        self.skip_existing = skip_existing;
+        self
+    }
+}
+
+/// An update on one of the models of a bulk pull.
+#[derive(Debug)]
+pub enum BulkPullEvent {
+    /// The model is already present locally and is not pulled.
+    Skipped { model: String },
+    /// The aggregated progress of the pull of the model.
+    Progress {
+        model: String,
+        progress: ModelProgress,
+    },
+    /// The pull of the model was interrupted and is restarted.
+    Retrying {
+        model: String,
+        attempt: u32,
+        error: OllamaError,
+    },
+    /// The model was pulled successfully.
+    Completed { model: String },
+    /// The pull of the model failed for good.
+    Failed { model: String, error: OllamaError },
+}
+
+impl BulkPullEvent {
+    /// The name of the model this event is about.
+    pub fn model(&self) -> &str {
+        match self {
+            BulkPullEvent::Skipped { model }
+            | BulkPullEvent::Progress { model, .. }
+            | BulkPullEvent::Retrying { model, .. }
+            | BulkPullEvent::Completed { model }
+            | BulkPullEvent::Failed { model, .. } => model,
+        }
+    }
+}
+
+/// The final outcome of the pull of a model.
+#[derive(Debug)]
+pub enum PullOutcome {
+    Skipped,
+    Completed,
+    Failed(OllamaError),
+}
+
+impl Ollama {
+    /// Pull several models, a few at a time, retrying interrupted pulls and skipping the
+    /// models already present locally.
+    /// Returns a stream of the events of every model, which ends once all of them are done.
+    /// Dropping the stream stops the pulls.
+    pub async fn pull_models_stream(
+        &self,
+        request: BulkPullRequest,
+    ) -> crate::error::Result<BulkPullEventStream> {
+        let local_models = if request.skip_existing {
+            self.list_local_models().await?
+        } else {
+            vec![]
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let semaphore = Arc::new(Semaphore::new(request.concurrency));
+        let mut tasks = JoinSet::new();
+
+        for target in request.models {
+            let tx = tx.clone();
+
+            if local_models.iter().any(|m| target.is_satisfied_by(m)) {
+                let _ = tx.send(BulkPullEvent::Skipped { model: target.name });
+                continue;
+            }
+
+            let ollama = self.clone();
+            let semaphore = semaphore.clone();
+            let (max_retries, retry_delay) = (request.max_retries, request.retry_delay);
+            let allow_insecure = request.allow_insecure;
+
+            tasks.spawn(async move {
+                let Ok(_permit) = semaphore.acquire_owned().await else {
+                    return;
+                };
+
+                let event = ollama
+                    .pull_with_retries(&target.name, allow_insecure, max_retries, retry_delay, &tx)
+                    .await;
+                let _ = tx.send(event);
+            });
+        }
+
+        Ok(Box::pin(BulkPullEvents {
+            events: UnboundedReceiverStream::new(rx),
+            _tasks: tasks,
+        }))
+    }
+
+    /// Same as [`Ollama::pull_models_stream`], only returning the outcome of each model once all of them are done.
+    pub async fn pull_models(
+        &self,
+        request: BulkPullRequest,
+    ) -> crate::error::Result<Vec<(String, PullOutcome)>> {
+        let mut outcomes: Vec<(String, PullOutcome)> = vec![];
+
+        let mut events = self.pull_models_stream(request).await?;
+        while let Some(event) = events.next().await {
+            let outcome = match event {
+                BulkPullEvent::Skipped { model } => (model, PullOutcome::Skipped),
+                BulkPullEvent::Completed { model } => (model, PullOutcome::Completed),
+                BulkPullEvent::Failed { model, error } => (model, PullOutcome::Failed(error)),
+                _ => continue,
+            };
+            outcomes.push(outcome);
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Pulls a model, sending its progress to `tx`, and returns its final event.
+    async fn pull_with_retries(
+        &self,
+        model: &str,
+        allow_insecure: bool,
+        max_retries: u32,
+        retry_delay: Duration,
+        tx: &mpsc::UnboundedSender<BulkPullEvent>,
+    ) -> BulkPullEvent {
+        // Kept across attempts, as the server resumes the layers where they stopped
+        let mut tracker = ProgressTracker::new();
+        let mut attempt = 0;
+
+        loop {
+            let error = match self
+                .pull_once(model, allow_insecure, &mut tracker, tx)
+                .await
+            {
+                Ok(()) => {
+                    return BulkPullEvent::Completed {
+                        model: model.to_string(),
+                    }
+                }
+                Err(e) => e,
+            };
+
+            if attempt >= max_retries || !is_interruption(&error) {
+                return BulkPullEvent::Failed {
+                    model: model.to_string(),
+                    error,
+                };
+            }
+
+            attempt += 1;
+            let _ = tx.send(BulkPullEvent::Retrying {
+                model: model.to_string(),
+                attempt,
+                error,
+            });
+
+            let sleep = self
+                .cancellable(async {
+                    tokio::time::sleep(retry_delay).await;
+                    Ok(())
+                })
+                .await;
+            if let Err(error) = sleep {
+                return BulkPullEvent::Failed {
+                    model: model.to_string(),
+                    error,
+                };
+            }
+        }
+    }
+
+    async fn pull_once(
+        &self,
+        model: &str,
+        allow_insecure: bool,
+        tracker: &mut ProgressTracker,
+        tx: &mpsc::UnboundedSender<BulkPullEvent>,
+    ) -> crate::error::Result<()> {
+        let mut stream = self
+            .pull_model_stream(model.to_string(), allow_insecure)
+            .await?;
+
+        let mut success = false;
+        while let Some(status) = stream.next().await {
+            let progress = tracker.update(&status?);
+            success = progress.is_success();
+
+            let _ = tx.send(BulkPullEvent::Progress {
+                model: model.to_string(),
+                progress,
+            });
+        }
+
+        if success {
+            Ok(())
+        } else {
+            Err(OllamaError::Other(format!(
+                "The pull of {} ended before completing",
+                model
+            )))
+        }
+    }
+}
+
+/// Whether a pull failed because it was interrupted, rather than because it cannot succeed.
+fn is_interruption(error: &OllamaError) -> bool {
+    match error {
+        OllamaError::Cancelled | OllamaError::JsonError(_) => false,
+        OllamaError::ApiError(e) => e.is_retryable(),
+        // Sent by the server when the model does not exist in the registry
+        OllamaError::InternalError(e) => !e.message.contains("file does not exist"),
+        _ => true,
+    }
+}
+
+fn trim_digest(digest: &str) -> &str {
+    digest.trim_start_matches("sha256:")
+}
@@ -0,0 +1,83 @@
+use serde::Deserialize;
+
+use crate::{error::OllamaError, Ollama};
+
+use super::{show_info::ShowModelInfoRequest, ModelCapability, ModelName};
+
+/// The part of the model information needed to check requests, so that the rest of it,
+/// such as the Modelfile, does not have to be parsed.
+#[derive(Deserialize)]
+struct ModelCapabilities {
+    #[serde(default)]
+    capabilities: Vec<ModelCapability>,
+}
+
+impl Ollama {
+    /// Returns the capabilities of a model, only requesting them the first time.
+    ///
+    /// Servers too old to report the capabilities of models return an empty list. Names of the
+    /// same model, such as `llama3` and `llama3:latest`, share their cache entry.
+    pub async fn model_capabilities(
+        &self,
+        model_name: impl Into<String>,
+    ) -> crate::error::Result<Vec<ModelCapability>> {
+        let model_name = model_name.into();
+        let key = ModelName::parse(&model_name)?;
+
+        if let Some(capabilities) = self.model_capabilities.lock().unwrap().get(&key) {
+            return Ok(capabilities.clone());
+        }
+
+        let url = format!("{}api/show", self.url_str());
+        let serialized = serde_json::to_string(&ShowModelInfoRequest::new(model_name))?;
+        let builder = self.reqwest_client.post(url).body(serialized);
+
+        let bytes = self.send_request(builder, true).await?;
+        let capabilities = serde_json::from_slice::<ModelCapabilities>(&bytes)?.capabilities;
+        self.model_capabilities
+            .lock()
+            .unwrap()
+            .insert(key, capabilities.clone());
+
+        Ok(capabilities)
+    }
+
+    /// Forgets the cached capabilities of every model, for instance after a model was pulled again.
+    pub fn clear_model_capabilities_cache(&self) {
+        self.model_capabilities.lock().unwrap().clear();
+    }
+
+    /// Whether requests check that the model supports the features they use before being sent.
+    ///
+    /// When enabled, the capabilities of each model are fetched once and cached, and a request
+    /// the model cannot serve, such as images sent to a model without vision, fails with
+    /// [`OllamaError::UnsupportedModelCapability`]. (Default: false)
+    pub fn set_validate_model_capabilities(&mut self, validate_model_capabilities: bool) {
+        self.validate_model_capabilities = validate_model_capabilities;
+    }
+
+    /// Fails if model capability checks are enabled and the model does not have one of `capabilities`.
+    pub(crate) async fn validate_model_capabilities(
+        &self,
+        model_name: &str,
+        capabilities: &[ModelCapability],
+    ) -> crate::error::Result<()> {
+        if !self.validate_model_capabilities || capabilities.is_empty() {
+            return Ok(());
+        }
+
+        let supported = self.model_capabilities(model_name).await?;
+        // Nothing to check against
+        if supported.is_empty() {
+            return Ok(());
+        }
+
+        match capabilities.iter().find(|c| !supported.contains(c)) {
+            Some(capability) => Err(OllamaError::UnsupportedModelCapability {
+                model: model_name.to_string(),
+                capability: capability.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
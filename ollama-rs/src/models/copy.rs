@@ -1,33 +1,26 @@
 use serde::Serialize;
 
-use crate::{error::OllamaError, Ollama};
+use crate::Ollama;
 
 impl Ollama {
     /// Copy a model. Creates a model with another name from an existing model.
     pub async fn copy_model(
         &self,
-        source: String,
-        destination: String,
+        source: impl Into<String>,
+        destination: impl Into<String>,
     ) -> crate::error::Result<()> {
         let request = CopyModelRequest {
-            source,
-            destination,
+            source: source.into(),
+            destination: destination.into(),
         };
 
         let url = format!("{}api/copy", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
+        self.send_request(builder, true).await?;
 
-        let res = builder.body(serialized).send().await?;
-
-        if res.status().is_success() {
-            Ok(())
-        } else {
-            Err(OllamaError::Other(res.text().await?))
-        }
+        Ok(())
     }
 }
 
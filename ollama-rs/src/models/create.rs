@@ -1,6 +1,18 @@
+use std::collections::HashMap;
+#[cfg(feature = "stream")]
+use std::path::{Path, PathBuf};
+
 use serde::{Deserialize, Serialize};
 
-use crate::{error::OllamaError, generation::chat::ChatMessage, Ollama};
+#[cfg(feature = "modelfile")]
+use modelfile::modelfile::Modelfile;
+
+#[cfg(feature = "modelfile")]
+use crate::error::OllamaError;
+use crate::{
+    generation::chat::{ChatMessage, MessageRole},
+    Ollama,
+};
 
 use super::ModelOptions;
 
@@ -15,78 +27,153 @@ impl Ollama {
     #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
     #[cfg(feature = "stream")]
     /// Create a model with streaming, meaning that each new status will be streamed.
+    ///
+    /// Local files added with [`CreateModelRequest::local_weights`] and
+    /// [`CreateModelRequest::local_adapter`] are uploaded first, see [`Ollama::upload_local_files`].
     pub async fn create_model_stream(
         &self,
         mut request: CreateModelRequest,
     ) -> crate::error::Result<CreateModelStatusStream> {
-        use tokio_stream::StreamExt;
-
-        use crate::error::OllamaError;
-
+        if let Some(parameters) = &request.parameters {
+            parameters.validate()?;
+        }
+        self.upload_local_files(&mut request).await?;
         request.stream = true;
 
         let url = format!("{}api/create", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        let res = builder.body(serialized).send().await?;
-
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(res.text().await?));
-        }
+        let res = self.send_stream_request(builder, false).await?;
 
-        let stream = Box::new(res.bytes_stream().map(|res| match res {
-            Ok(bytes) => {
-                let res = serde_json::from_slice::<CreateModelStatus>(&bytes);
-                match res {
-                    Ok(res) => Ok(res),
-                    Err(e) => {
-                        let err =
-                            serde_json::from_slice::<crate::error::InternalOllamaError>(&bytes);
-                        match err {
-                            Ok(err) => Err(OllamaError::InternalError(err)),
-                            Err(_) => Err(OllamaError::from(e)),
-                        }
-                    }
-                }
-            }
-            Err(e) => Err(OllamaError::Other(format!(
-                "Failed to read response: {}",
-                e
-            ))),
-        }));
-
-        Ok(std::pin::Pin::from(stream))
+        Ok(self.ndjson_stream(res))
     }
 
     /// Create a model with a single response, only the final status will be returned.
     pub async fn create_model(
         &self,
-        request: CreateModelRequest,
+        #[allow(unused_mut)] mut request: CreateModelRequest,
     ) -> crate::error::Result<CreateModelStatus> {
+        if let Some(parameters) = &request.parameters {
+            parameters.validate()?;
+        }
+        #[cfg(feature = "stream")]
+        self.upload_local_files(&mut request).await?;
+
         let url = format!("{}api/create", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
+        let bytes = self.send_request(builder, false).await?;
+        let res = serde_json::from_slice::<CreateModelStatus>(&bytes)?;
+
+        Ok(res)
+    }
 
-        let res = builder.body(serialized).send().await?;
+    #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+    #[cfg(feature = "stream")]
+    /// Upload the local files of a request as blobs, and add their digests to its `files` and `adapters`.
+    ///
+    /// Only the blobs missing from the server are uploaded. This is done by the create methods,
+    /// and only needs to be called directly to upload the files ahead of time.
+    pub async fn upload_local_files(
+        &self,
+        request: &mut CreateModelRequest,
+    ) -> crate::error::Result<()> {
+        if let Some(path) = request.local_weights.take() {
+            let files = self.upload_model_files(&path).await?;
+            request.files.get_or_insert_with(HashMap::new).extend(files);
+        }
 
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(res.text().await?));
+        for path in std::mem::take(&mut request.local_adapters) {
+            let adapters = self.upload_model_files(&path).await?;
+            request
+                .adapters
+                .get_or_insert_with(HashMap::new)
+                .extend(adapters);
         }
 
-        let res = res.bytes().await?;
-        let res = serde_json::from_slice::<CreateModelStatus>(&res)?;
+        Ok(())
+    }
 
-        Ok(res)
+    /// Uploads a model file, or the relevant files of a model directory,
+    /// and returns a map of their file names to their digests.
+    #[cfg(feature = "stream")]
+    async fn upload_model_files(
+        &self,
+        path: &Path,
+    ) -> crate::error::Result<HashMap<String, String>> {
+        let mut files = HashMap::new();
+
+        for file in model_files(path).await? {
+            let name = match file.strip_prefix(path) {
+                Ok(name) if !name.as_os_str().is_empty() => name,
+                // A single file is named after itself
+                _ => Path::new(file.file_name().unwrap_or_default()),
+            };
+            let name = name.to_string_lossy().replace('\\', "/");
+
+            let digest = self.push_blob(&file).await?;
+            files.insert(name, digest);
+        }
+
+        Ok(files)
     }
 }
 
+/// Lists the files to upload for a model file or directory, the same way the Ollama CLI does:
+/// the safetensors or GGUF weights, along with the JSON configuration files and the tokenizer.
+#[cfg(feature = "stream")]
+async fn model_files(path: &Path) -> crate::error::Result<Vec<PathBuf>> {
+    use crate::error::OllamaError;
+
+    if !tokio::fs::metadata(path).await?.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut entries = vec![];
+    let mut dir = tokio::fs::read_dir(path).await?;
+    while let Some(entry) = dir.next_entry().await? {
+        if entry.file_type().await?.is_file() {
+            entries.push(entry.path());
+        }
+    }
+    entries.sort();
+
+    let has_extension =
+        |p: &PathBuf, ext: &str| p.extension().is_some_and(|e| e.eq_ignore_ascii_case(ext));
+
+    let mut files: Vec<PathBuf> = entries
+        .iter()
+        .filter(|p| has_extension(p, "safetensors"))
+        .cloned()
+        .collect();
+    if files.is_empty() {
+        files = entries
+            .iter()
+            .filter(|p| has_extension(p, "gguf"))
+            .cloned()
+            .collect();
+    }
+    if files.is_empty() {
+        return Err(OllamaError::Other(format!(
+            "No safetensors or GGUF files found in {}",
+            path.display()
+        )));
+    }
+
+    files.extend(
+        entries
+            .iter()
+            .filter(|p| {
+                has_extension(p, "json") || p.file_name().is_some_and(|n| n == "tokenizer.model")
+            })
+            .cloned(),
+    );
+
+    Ok(files)
+}
+
 #[derive(Serialize)]
 pub enum QuantizationType {
     #[serde(rename = "q2_K")]
@@ -130,10 +217,10 @@ pub struct CreateModelRequest {
     from_model: Option<String>,
     /// A dictionary of file names to SHA256 digests of blobs to create the model from
     #[serde(skip_serializing_if = "Option::is_none")]
-    files: Option<std::collections::HashMap<String, String>>,
+    files: Option<HashMap<String, String>>,
     /// A dictionary of file names to SHA256 digests of blobs for LORA adapters
     #[serde(skip_serializing_if = "Option::is_none")]
-    adapters: Option<std::collections::HashMap<String, String>>,
+    adapters: Option<HashMap<String, String>>,
     /// The prompt template for the model
     #[serde(skip_serializing_if = "Option::is_none")]
     template: Option<String>,
@@ -153,12 +240,20 @@ pub struct CreateModelRequest {
     /// Quantize a non-quantized model
     #[serde(skip_serializing_if = "Option::is_none")]
     quantize: Option<QuantizationType>,
+    /// A local model file or directory, uploaded before creating the model
+    #[cfg(feature = "stream")]
+    #[serde(skip)]
+    local_weights: Option<PathBuf>,
+    /// Local LORA adapter files or directories, uploaded before creating the model
+    #[cfg(feature = "stream")]
+    #[serde(skip)]
+    local_adapters: Vec<PathBuf>,
 }
 
 impl CreateModelRequest {
-    pub fn new(model_name: String) -> Self {
+    pub fn new(model_name: impl Into<String>) -> Self {
         Self {
-            model_name,
+            model_name: model_name.into(),
             from_model: None,
             files: None,
             adapters: None,
@@ -169,20 +264,24 @@ impl CreateModelRequest {
             messages: None,
             stream: false,
             quantize: None,
+            #[cfg(feature = "stream")]
+            local_weights: None,
+            #[cfg(feature = "stream")]
+            local_adapters: vec![],
         }
     }
 
-    pub fn from_model(mut self, from_model: String) -> Self {
-        self.from_model = Some(from_model);
+    pub fn from_model(mut self, from_model: impl Into<String>) -> Self {
+        self.from_model = Some(from_model.into());
         self
     }
 
-    pub fn files(mut self, files: std::collections::HashMap<String, String>) -> Self {
+    pub fn files(mut self, files: HashMap<String, String>) -> Self {
         self.files = Some(files);
         self
     }
 
-    pub fn adapters(mut self, adapters: std::collections::HashMap<String, String>) -> Self {
+    pub fn adapters(mut self, adapters: HashMap<String, String>) -> Self {
         self.adapters = Some(adapters);
         self
     }
@@ -221,11 +320,103 @@ impl CreateModelRequest {
         self.quantize = Some(quantize);
         self
     }
+
+    /// The request as a Modelfile, for instance to keep it along with the model or to create it with the CLI.
+    ///
+    /// Files and adapters are referenced by their file names. The quantization is not part of
+    /// the Modelfile, and is set with `ollama create --quantize` instead.
+    pub fn to_modelfile(&self) -> String {
+        let mut modelfile = String::new();
+
+        if let Some(from_model) = &self.from_model {
+            modelfile.push_str(&format!("FROM {}\n", from_model));
+        }
+        for file in sorted_names(&self.files) {
+            modelfile.push_str(&format!("FROM {}\n", file));
+        }
+        for adapter in sorted_names(&self.adapters) {
+            modelfile.push_str(&format!("ADAPTER {}\n", adapter));
+        }
+        if let Some(template) = &self.template {
+            modelfile.push_str(&format!("TEMPLATE {}\n", multiline(template)));
+        }
+        if let Some(system) = &self.system {
+            modelfile.push_str(&format!("SYSTEM {}\n", multiline(system)));
+        }
+        if let Some(parameters) = &self.parameters {
+            modelfile.push_str(&parameters.to_modelfile());
+        }
+        for license in self.license.iter().flatten() {
+            modelfile.push_str(&format!("LICENSE {}\n", multiline(license)));
+        }
+        for message in self.messages.iter().flatten() {
+            let role = match message.role {
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+                MessageRole::System => "system",
+                MessageRole::Tool => "tool",
+            };
+            modelfile.push_str(&format!(
+                "MESSAGE {} {}\n",
+                role,
+                multiline(&message.content)
+            ));
+        }
+
+        modelfile
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "modelfile")))]
+    #[cfg(feature = "modelfile")]
+    /// Same as [`CreateModelRequest::to_modelfile`], parsed as a `Modelfile`.
+    pub fn modelfile(&self) -> crate::error::Result<Modelfile> {
+        self.to_modelfile()
+            .parse()
+            .map_err(|e| OllamaError::Other(format!("Invalid Modelfile: {}", e)))
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+    #[cfg(feature = "stream")]
+    /// Create the model from a local GGUF file, or a directory of safetensors or GGUF weights.
+    /// The files are uploaded when the request is sent, and added to `files`.
+    pub fn local_weights(mut self, path: impl Into<PathBuf>) -> Self {
+        self.local_weights = Some(path.into());
+        self
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+    #[cfg(feature = "stream")]
+    /// Add a local LORA adapter file or directory to the model.
+    /// The files are uploaded when the request is sent, and added to `adapters`.
+    pub fn local_adapter(mut self, path: impl Into<PathBuf>) -> Self {
+        self.local_adapters.push(path.into());
+        self
+    }
 }
 
 /// A create model status response from Ollama.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct CreateModelStatus {
     #[serde(rename = "status")]
     pub message: String,
+    /// The digest of the layer being processed, such as while quantizing
+    pub digest: Option<String>,
+    pub total: Option<u64>,
+    pub completed: Option<u64>,
+}
+
+/// The keys of a map of file names to digests, in order.
+fn sorted_names(files: &Option<HashMap<String, String>>) -> Vec<&String> {
+    let mut names: Vec<_> = files.iter().flat_map(|f| f.keys()).collect();
+    names.sort();
+    names
+}
+
+/// Wraps a value spanning several lines in triple quotes.
+fn multiline(value: &str) -> String {
+    if value.contains('\n') || value.contains('"') {
+        format!("\"\"\"{}\"\"\"", value)
+    } else {
+        value.to_string()
+    }
 }
@@ -1,26 +1,21 @@
 use serde::Serialize;
 
-use crate::{error::OllamaError, Ollama};
+use crate::Ollama;
 
 impl Ollama {
     /// Delete a model and its data.
-    pub async fn delete_model(&self, model_name: String) -> crate::error::Result<()> {
-        let request = DeleteModelRequest { model_name };
+    pub async fn delete_model(&self, model_name: impl Into<String>) -> crate::error::Result<()> {
+        let request = DeleteModelRequest {
+            model_name: model_name.into(),
+        };
 
         let url = format!("{}api/delete", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.delete(url);
+        let builder = self.reqwest_client.delete(url).body(serialized);
 
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
+        self.send_request(builder, false).await?;
 
-        let res = builder.body(serialized).send().await?;
-
-        if res.status().is_success() {
-            Ok(())
-        } else {
-            Err(OllamaError::Other(res.text().await?))
-        }
+        Ok(())
     }
 }
 
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-use crate::{error::OllamaError, Ollama};
+use crate::Ollama;
 
 use super::LocalModel;
 
@@ -9,17 +9,8 @@ impl Ollama {
         let url = format!("{}api/tags", self.url_str());
         let builder = self.reqwest_client.get(url);
 
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
-
-        let res = builder.send().await?;
-
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(res.text().await?));
-        }
-
-        let res = res.bytes().await?;
-        let res = serde_json::from_slice::<ListLocalModelsResponse>(&res)?;
+        let bytes = self.send_request(builder, true).await?;
+        let res = serde_json::from_slice::<ListLocalModelsResponse>(&bytes)?;
 
         Ok(res.models)
     }
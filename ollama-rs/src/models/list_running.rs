@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+use crate::Ollama;
+
+use super::RunningModel;
+
+impl Ollama {
+    /// List the models that are currently loaded in memory.
+    pub async fn list_running_models(&self) -> crate::error::Result<Vec<RunningModel>> {
+        let url = format!("{}api/ps", self.url_str());
+        let builder = self.reqwest_client.get(url);
+
+        let bytes = self.send_request(builder, true).await?;
+        let res = serde_json::from_slice::<ListRunningModelsResponse>(&bytes)?;
+
+        Ok(res.models)
+    }
+}
+
+/// A response from Ollama containing a list of running models.
+#[derive(Debug, Clone, Deserialize)]
+struct ListRunningModelsResponse {
+    models: Vec<RunningModel>,
+}
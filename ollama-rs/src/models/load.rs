@@ -0,0 +1,89 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::{error::OllamaError, generation::parameters::KeepAlive, Ollama};
+
+use super::is_same_model;
+
+/// How long `unload_model` waits for the model to be evicted.
+const UNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+/// How often `unload_model` checks whether the model was evicted.
+const UNLOAD_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+impl Ollama {
+    /// Load a model into memory without generating anything.
+    /// Returns once the model is loaded.
+    /// - `model_name` - The name of the model to load.
+    /// - `keep_alive` - How long the model stays loaded after this request.
+    pub async fn load_model(
+        &self,
+        model_name: impl Into<String>,
+        keep_alive: KeepAlive,
+    ) -> crate::error::Result<()> {
+        let request = LoadModelRequest {
+            model_name: model_name.into(),
+            keep_alive,
+            stream: false,
+        };
+
+        self.send_load_request(&request).await
+    }
+
+    /// Unload a model from memory.
+    /// Returns once the model is no longer listed by [`Ollama::list_running_models`].
+    /// - `model_name` - The name of the model to unload.
+    pub async fn unload_model(&self, model_name: impl Into<String>) -> crate::error::Result<()> {
+        let request = LoadModelRequest {
+            model_name: model_name.into(),
+            keep_alive: KeepAlive::UnloadOnCompletion,
+            stream: false,
+        };
+
+        self.send_load_request(&request).await?;
+
+        // The server answers as soon as the model is scheduled for eviction
+        let start = Instant::now();
+        loop {
+            let running = self.list_running_models().await?;
+            if !running
+                .iter()
+                .any(|m| is_same_model(&m.name, &request.model_name))
+            {
+                return Ok(());
+            }
+
+            if start.elapsed() >= UNLOAD_TIMEOUT {
+                return Err(OllamaError::Other(format!(
+                    "Model {} was not unloaded after {:?}",
+                    request.model_name, UNLOAD_TIMEOUT
+                )));
+            }
+
+            self.cancellable(async {
+                tokio::time::sleep(UNLOAD_POLL_INTERVAL).await;
+                Ok(())
+            })
+            .await?;
+        }
+    }
+
+    async fn send_load_request(&self, request: &LoadModelRequest) -> crate::error::Result<()> {
+        let url = format!("{}api/generate", self.url_str());
+        let serialized = serde_json::to_string(request)?;
+        let builder = self.reqwest_client.post(url).body(serialized);
+
+        self.send_request(builder, true).await?;
+
+        Ok(())
+    }
+}
+
+/// An empty generation request, only used to load or unload a model.
+#[derive(Serialize)]
+struct LoadModelRequest {
+    #[serde(rename = "model")]
+    model_name: String,
+    keep_alive: KeepAlive,
+    stream: bool,
+}
@@ -0,0 +1,247 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::OllamaError;
+
+/// The registry models are pulled from when the name does not specify one.
+pub const DEFAULT_REGISTRY: &str = "registry.ollama.ai";
+/// The namespace of the official models.
+pub const DEFAULT_NAMESPACE: &str = "library";
+/// The tag used when the name does not specify one.
+pub const DEFAULT_TAG: &str = "latest";
+
+/// A parsed model reference, in the form `[registry/][namespace/]model[:tag][@digest]`.
+///
+/// Missing parts take the same defaults as in Ollama, and names are compared case-insensitively
+/// once normalized, so `llama3`, `llama3:latest` and `registry.ollama.ai/library/llama3:latest`
+/// are the same model.
+///
+/// `ModelName` can be used anywhere the crate takes a model name, and is displayed in its
+/// shortest form.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_rs::models::ModelName;
+///
+/// let name: ModelName = "llama3".parse().unwrap();
+///
+/// assert_eq!(name.tag(), "latest");
+/// assert_eq!(name.to_string(), "llama3:latest");
+/// assert_eq!(name.to_full_string(), "registry.ollama.ai/library/llama3:latest");
+/// assert_eq!(name, "Llama3:latest".parse().unwrap());
+///
+/// let name: ModelName = "example.com:5000/me/my-model:7b@sha256:b0de3c".parse().unwrap();
+/// assert_eq!(name.registry(), "example.com:5000");
+/// assert_eq!(name.digest(), Some("sha256:b0de3c"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ModelName {
+    registry: String,
+    namespace: String,
+    model: String,
+    tag: String,
+    digest: Option<String>,
+}
+
+impl ModelName {
+    /// Parses a model name, applying the defaults for the missing parts.
+    pub fn parse(name: &str) -> crate::error::Result<Self> {
+        let invalid =
+            |reason: &str| OllamaError::InvalidModelName(format!("{:?}: {}", name, reason));
+
+        let (rest, digest) = match name.rsplit_once('@') {
+            Some((rest, digest)) => (rest, Some(digest)),
+            None => (name, None),
+        };
+
+        // The tag is after the last colon, unless it is the port of the registry
+        let (rest, tag) = match rest.rsplit_once(':') {
+            Some((rest, tag)) if !tag.contains('/') => (rest, Some(tag)),
+            _ => (rest, None),
+        };
+
+        let mut parts = rest.rsplitn(3, '/');
+        let model = parts.next().unwrap_or_default();
+        let namespace = parts.next();
+        let registry = parts.next();
+
+        if !is_valid_part(model, 80, &['_', '-', '.']) {
+            return Err(invalid("invalid model"));
+        }
+        if namespace.is_some_and(|n| !is_valid_part(n, 80, &['_', '-'])) {
+            return Err(invalid("invalid namespace"));
+        }
+        if registry.is_some_and(|r| !is_valid_part(r, 350, &['_', '-', '.', ':'])) {
+            return Err(invalid("invalid registry"));
+        }
+        if tag.is_some_and(|t| !is_valid_part(t, 80, &['_', '-', '.'])) {
+            return Err(invalid("invalid tag"));
+        }
+        if digest.is_some_and(|d| !is_valid_digest(d)) {
+            return Err(invalid("invalid digest"));
+        }
+
+        Ok(Self {
+            registry: registry.unwrap_or(DEFAULT_REGISTRY).to_string(),
+            namespace: namespace.unwrap_or(DEFAULT_NAMESPACE).to_string(),
+            model: model.to_string(),
+            tag: tag.unwrap_or(DEFAULT_TAG).to_string(),
+            // Ollama accepts both `sha256:<hex>` and `sha256-<hex>`
+            digest: digest.map(|d| d.replacen('-', ":", 1)),
+        })
+    }
+
+    pub fn registry(&self) -> &str {
+        &self.registry
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    /// Returns the same name with another tag.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = tag.into();
+        self
+    }
+
+    /// The full name, with every default made explicit.
+    pub fn to_full_string(&self) -> String {
+        let mut name = format!(
+            "{}/{}/{}:{}",
+            self.registry, self.namespace, self.model, self.tag
+        );
+        if let Some(digest) = &self.digest {
+            name.push('@');
+            name.push_str(digest);
+        }
+        name
+    }
+
+    /// Returns whether `name` refers to this model. Names that cannot be parsed never match.
+    pub fn matches(&self, name: &str) -> bool {
+        Self::parse(name).is_ok_and(|name| name == *self)
+    }
+
+    fn normalized(&self) -> [String; 4] {
+        [
+            self.registry.to_lowercase(),
+            self.namespace.to_lowercase(),
+            self.model.to_lowercase(),
+            self.tag.to_lowercase(),
+        ]
+    }
+}
+
+impl PartialEq for ModelName {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+            && self.digest.as_ref().map(|d| d.to_lowercase())
+                == other.digest.as_ref().map(|d| d.to_lowercase())
+    }
+}
+
+impl Eq for ModelName {}
+
+impl Hash for ModelName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state);
+        self.digest.as_ref().map(|d| d.to_lowercase()).hash(state);
+    }
+}
+
+impl fmt::Display for ModelName {
+    /// Displays the shortest form of the name, omitting the default registry and namespace.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.registry != DEFAULT_REGISTRY {
+            write!(f, "{}/{}/", self.registry, self.namespace)?;
+        } else if self.namespace != DEFAULT_NAMESPACE {
+            write!(f, "{}/", self.namespace)?;
+        }
+        write!(f, "{}:{}", self.model, self.tag)?;
+        if let Some(digest) = &self.digest {
+            write!(f, "@{}", digest)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ModelName {
+    type Err = OllamaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for ModelName {
+    type Error = OllamaError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<ModelName> for String {
+    fn from(name: ModelName) -> Self {
+        name.to_string()
+    }
+}
+
+impl From<&ModelName> for String {
+    fn from(name: &ModelName) -> Self {
+        name.to_string()
+    }
+}
+
+impl Serialize for ModelName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ModelName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A part must start with a letter or a digit, and only contain letters, digits and `extra`.
+fn is_valid_part(part: &str, max_len: usize, extra: &[char]) -> bool {
+    let mut chars = part.chars();
+
+    !part.is_empty()
+        && part.len() <= max_len
+        && chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && chars.all(|c| c.is_ascii_alphanumeric() || extra.contains(&c))
+}
+
+fn is_valid_digest(digest: &str) -> bool {
+    match digest.split_once([':', '-']) {
+        Some((algorithm, hex)) => {
+            algorithm.eq_ignore_ascii_case("sha256")
+                && !hex.is_empty()
+                && hex.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        None => false,
+    }
+}
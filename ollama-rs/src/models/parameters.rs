@@ -0,0 +1,286 @@
+use std::str::FromStr;
+
+#[cfg(feature = "modelfile")]
+use modelfile::modelfile::Modelfile;
+
+use crate::error::OllamaError;
+
+use super::{ModelInfo, ModelOptions};
+
+/// An option whose value differs between two sets of options, see [`ModelOptions::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionChange {
+    /// The name of the option, as in a Modelfile.
+    pub option: &'static str,
+    /// The previous values of the option, empty if it was not set.
+    pub from: Vec<String>,
+    /// The new values of the option.
+    pub to: Vec<String>,
+}
+
+/// Implements the conversions for every option with a single value, which `stop` is not.
+macro_rules! impl_parameters {
+    ($($option:ident),* $(,)?) => {
+        impl ModelOptions {
+            fn set_parameter(&mut self, name: &str, value: &str) -> crate::error::Result<()> {
+                match name {
+                    $(stringify!($option) => {
+                        self.$option = Some(parse_value(stringify!($option), value)?);
+                    })*
+                    "stop" => self.stop.get_or_insert_with(Vec::new).push(value.to_string()),
+                    // Options unknown to this version of the crate
+                    _ => {}
+                }
+                Ok(())
+            }
+
+            /// The options as Modelfile `PARAMETER` name and value pairs, with one pair per stop sequence.
+            pub fn to_parameters(&self) -> Vec<(&'static str, String)> {
+                let mut parameters = vec![];
+                $(
+                    if let Some(value) = &self.$option {
+                        parameters.push((stringify!($option), value.to_string()));
+                    }
+                )*
+                for stop in self.stop.iter().flatten() {
+                    parameters.push(("stop", quote(stop)));
+                }
+                parameters
+            }
+        }
+    };
+}
+
+impl_parameters!(
+    mirostat,
+    mirostat_eta,
+    mirostat_tau,
+    num_ctx,
+    num_gqa,
+    num_gpu,
+    num_thread,
+    repeat_last_n,
+    repeat_penalty,
+    temperature,
+    seed,
+    tfs_z,
+    num_predict,
+    top_k,
+    top_p,
+    num_keep,
+    typical_p,
+    min_p,
+    presence_penalty,
+    frequency_penalty,
+    penalize_newline,
+    numa,
+    num_batch,
+    main_gpu,
+    use_mmap,
+    use_mlock,
+    low_vram,
+    vocab_only,
+);
+
+impl ModelOptions {
+    /// Parses parameters in the format of [`ModelInfo::parameters`], with one `name value` pair per line.
+    ///
+    /// Parameters unknown to this version of the crate are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ollama_rs::models::ModelOptions;
+    ///
+    /// let options = ModelOptions::from_parameters("stop \"<|eot_id|>\"\ntemperature 0.7").unwrap();
+    ///
+    /// assert_eq!(options.get_temperature(), Some(0.7));
+    /// assert_eq!(options.get_stop(), Some(&["<|eot_id|>".to_string()][..]));
+    /// ```
+    pub fn from_parameters(parameters: &str) -> crate::error::Result<Self> {
+        let mut options = Self::default();
+        for line in parameters.lines() {
+            if let Some((name, value)) = split_parameter(line) {
+                options.set_parameter(&name.to_lowercase(), &unquote(value))?;
+            }
+        }
+        Ok(options)
+    }
+
+    /// Parses the `PARAMETER` instructions of a Modelfile, ignoring the other instructions.
+    pub fn from_modelfile(modelfile: &str) -> crate::error::Result<Self> {
+        let mut options = Self::default();
+        let mut in_multiline = false;
+        for line in modelfile.lines() {
+            // Skip the content of multiline values, such as templates
+            if line.matches(r#"""""#).count() % 2 == 1 {
+                in_multiline = !in_multiline;
+                continue;
+            }
+            if in_multiline {
+                continue;
+            }
+
+            let Some((instruction, rest)) = split_parameter(line) else {
+                continue;
+            };
+            if instruction.eq_ignore_ascii_case("PARAMETER") {
+                if let Some((name, value)) = split_parameter(rest) {
+                    options.set_parameter(&name.to_lowercase(), &unquote(value))?;
+                }
+            }
+        }
+        Ok(options)
+    }
+
+    /// The options as Modelfile `PARAMETER` instructions, one per line.
+    pub fn to_modelfile(&self) -> String {
+        self.to_parameters()
+            .into_iter()
+            .map(|(name, value)| format!("PARAMETER {} {}\n", name, value))
+            .collect()
+    }
+
+    /// The options set in `other` whose value differs from the one in `self`, in other words what
+    /// changes once `other` is layered over `self` with [`ModelOptions::merge`].
+    ///
+    /// This is useful to compare the parameters of a model, see [`ModelInfo::options`],
+    /// with the options of a request.
+    pub fn diff(&self, other: &ModelOptions) -> Vec<OptionChange> {
+        let values = |options: &ModelOptions, option: &str| -> Vec<String> {
+            options
+                .to_parameters()
+                .into_iter()
+                .filter(|(name, _)| *name == option)
+                .map(|(_, value)| unquote(&value))
+                .collect()
+        };
+
+        let mut changes: Vec<OptionChange> = vec![];
+        for (option, _) in other.to_parameters() {
+            if changes.iter().any(|c| c.option == option) {
+                continue;
+            }
+
+            let (from, to) = (values(self, option), values(other, option));
+            if from != to {
+                changes.push(OptionChange { option, from, to });
+            }
+        }
+        changes
+    }
+}
+
+impl FromStr for ModelOptions {
+    type Err = OllamaError;
+
+    /// Same as [`ModelOptions::from_parameters`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_parameters(s)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "modelfile")))]
+#[cfg(feature = "modelfile")]
+impl TryFrom<&Modelfile> for ModelOptions {
+    type Error = OllamaError;
+
+    /// Reads the `PARAMETER` instructions of the Modelfile.
+    fn try_from(modelfile: &Modelfile) -> Result<Self, Self::Error> {
+        Self::from_modelfile(&modelfile.to_string())
+    }
+}
+
+impl ModelInfo {
+    /// The parameters of the model, parsed from [`ModelInfo::parameters`].
+    pub fn options(&self) -> crate::error::Result<ModelOptions> {
+        ModelOptions::from_parameters(&self.parameters)
+    }
+}
+
+fn parse_value<T: FromStr>(option: &'static str, value: &str) -> crate::error::Result<T> {
+    value.parse().map_err(|_| OllamaError::InvalidModelOption {
+        option,
+        reason: format!("cannot parse {:?}", value),
+    })
+}
+
+/// Splits a line into its first word and the rest, trimmed.
+fn split_parameter(line: &str) -> Option<(&str, &str)> {
+    let (name, value) = line.trim().split_once(char::is_whitespace)?;
+    Some((name, value.trim()))
+}
+
+/// Quotes a string value as Ollama prints it, escaping characters as Go does so that the value
+/// stays on one line.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() && c.is_ascii() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Removes the quotes around a string value and decodes its Go escape sequences.
+/// Unknown or invalid escape sequences are kept as they are.
+fn unquote(value: &str) -> String {
+    let Some(value) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    else {
+        return value.to_string();
+    };
+
+    let mut unquoted = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+
+        let rest = chars.as_str();
+        let (decoded, len) = match rest.chars().next() {
+            Some('a') => (Some('\x07'), 1),
+            Some('b') => (Some('\x08'), 1),
+            Some('f') => (Some('\x0c'), 1),
+            Some('n') => (Some('\n'), 1),
+            Some('r') => (Some('\r'), 1),
+            Some('t') => (Some('\t'), 1),
+            Some('v') => (Some('\x0b'), 1),
+            Some(c @ ('\\' | '"' | '\'')) => (Some(c), 1),
+            Some('x') => (decode_hex(rest, 2), 3),
+            Some('u') => (decode_hex(rest, 4), 5),
+            Some('U') => (decode_hex(rest, 8), 9),
+            _ => (None, 0),
+        };
+        match decoded {
+            Some(decoded) => {
+                unquoted.push(decoded);
+                chars = rest[len..].chars();
+            }
+            None => unquoted.push(c),
+        }
+    }
+    unquoted
+}
+
+/// Decodes the `digits` hexadecimal digits following the escape letter at the start of `escape`.
+fn decode_hex(escape: &str, digits: usize) -> Option<char> {
+    let hex = escape.get(1..1 + digits)?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    char::from_u32(u32::from_str_radix(hex, 16).ok()?)
+}
@@ -0,0 +1,256 @@
+use super::{create::CreateModelStatus, pull::PullModelStatus, push::PushModelStatus};
+
+/// A stream of `ModelProgress` objects, see [`track_progress`].
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+#[cfg(feature = "stream")]
+pub type ModelProgressStream =
+    std::pin::Pin<Box<dyn tokio_stream::Stream<Item = crate::error::Result<ModelProgress>> + Send>>;
+
+/// A status update sent while pulling, pushing or creating a model.
+pub trait ModelStatus {
+    /// The status message, such as `pulling manifest`.
+    fn message(&self) -> &str;
+    /// The digest of the layer being transferred, if any.
+    fn digest(&self) -> Option<&str>;
+    /// The size of the layer being transferred, in bytes.
+    fn total(&self) -> Option<u64>;
+    /// How many bytes of the layer were transferred.
+    fn completed(&self) -> Option<u64>;
+}
+
+macro_rules! impl_model_status {
+    ($($status:ty),*) => {
+        $(
+            impl ModelStatus for $status {
+                fn message(&self) -> &str {
+                    &self.message
+                }
+
+                fn digest(&self) -> Option<&str> {
+                    self.digest.as_deref()
+                }
+
+                fn total(&self) -> Option<u64> {
+                    self.total
+                }
+
+                fn completed(&self) -> Option<u64> {
+                    self.completed
+                }
+            }
+        )*
+    };
+}
+
+impl_model_status!(PullModelStatus, PushModelStatus, CreateModelStatus);
+
+/// The phase of a pull, push or model creation, recognized from a status message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelPhase {
+    /// `pulling manifest`
+    PullingManifest,
+    /// `retrieving manifest`
+    RetrievingManifest,
+    /// `pushing manifest`
+    PushingManifest,
+    /// Downloading a layer.
+    Downloading { digest: String },
+    /// Uploading a layer.
+    Uploading { digest: String },
+    /// Creating a new layer, or converting or quantizing the model.
+    Creating,
+    /// `verifying sha256 digest`
+    VerifyingDigest,
+    /// `writing manifest`
+    WritingManifest,
+    /// `removing any unused layers` / `removing unused layers`
+    RemovingUnusedLayers,
+    /// `success`
+    Success,
+    /// A status that is not recognized.
+    Other(String),
+}
+
+impl ModelPhase {
+    /// Recognizes the phase of a status update.
+    pub fn from_status(status: &impl ModelStatus) -> Self {
+        let message = status.message();
+
+        match (message, status.digest()) {
+            ("pulling manifest", _) => Self::PullingManifest,
+            ("retrieving manifest", _) => Self::RetrievingManifest,
+            ("pushing manifest", _) => Self::PushingManifest,
+            ("verifying sha256 digest", _) => Self::VerifyingDigest,
+            ("writing manifest", _) => Self::WritingManifest,
+            ("removing any unused layers" | "removing unused layers", _) => {
+                Self::RemovingUnusedLayers
+            }
+            ("success", _) => Self::Success,
+            (m, Some(digest)) if m.starts_with("pulling ") || m.starts_with("downloading ") => {
+                Self::Downloading {
+                    digest: digest.to_string(),
+                }
+            }
+            (m, Some(digest)) if m.starts_with("pushing ") || m.starts_with("uploading ") => {
+                Self::Uploading {
+                    digest: digest.to_string(),
+                }
+            }
+            (m, _)
+                if m.starts_with("creating ")
+                    || m.starts_with("using ")
+                    || m.starts_with("converting ")
+                    || m.starts_with("quantizing ")
+                    || m.starts_with("parsing ") =>
+            {
+                Self::Creating
+            }
+            (m, _) => Self::Other(m.to_string()),
+        }
+    }
+}
+
+/// The progress of the transfer of a single layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerProgress {
+    pub digest: String,
+    /// The size of the layer, in bytes.
+    pub total: u64,
+    /// How many bytes of the layer were transferred.
+    pub completed: u64,
+}
+
+/// The overall progress of a pull, push or model creation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelProgress {
+    /// The current phase.
+    pub phase: ModelPhase,
+    /// The layers seen so far, in the order they started.
+    pub layers: Vec<LayerProgress>,
+    /// The total size of the layers seen so far, in bytes.
+    pub total: u64,
+    /// How many bytes of the layers seen so far were transferred.
+    pub completed: u64,
+}
+
+impl ModelProgress {
+    /// The overall completion, between 0 and 100, if the size of any layer is known yet.
+    pub fn percentage(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.completed as f64 / self.total as f64 * 100.0)
+    }
+
+    /// Whether the operation succeeded.
+    pub fn is_success(&self) -> bool {
+        self.phase == ModelPhase::Success
+    }
+}
+
+/// Aggregates the status updates of a pull, push or model creation.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_rs::models::progress::{ModelPhase, ProgressTracker};
+/// use ollama_rs::models::pull::PullModelStatus;
+///
+/// let status: PullModelStatus = serde_json::from_str(
+///     r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a0746a1ec1a","total":100,"completed":25}"#,
+/// ).unwrap();
+///
+/// let mut tracker = ProgressTracker::new();
+/// let progress = tracker.update(&status);
+///
+/// assert!(matches!(progress.phase, ModelPhase::Downloading { .. }));
+/// assert_eq!(progress.percentage(), Some(25.0));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProgressTracker {
+    layers: Vec<LayerProgress>,
+}
+
+impl ProgressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes a status update into account and returns the resulting progress.
+    pub fn update(&mut self, status: &impl ModelStatus) -> ModelProgress {
+        let phase = ModelPhase::from_status(status);
+
+        if let Some(digest) = status.digest() {
+            let index = match self.layers.iter().position(|l| l.digest == digest) {
+                Some(index) => index,
+                None => {
+                    self.layers.push(LayerProgress {
+                        digest: digest.to_string(),
+                        total: 0,
+                        completed: 0,
+                    });
+                    self.layers.len() - 1
+                }
+            };
+
+            let layer = &mut self.layers[index];
+            if let Some(total) = status.total() {
+                layer.total = total;
+            }
+            if let Some(completed) = status.completed() {
+                layer.completed = completed;
+            }
+        }
+
+        // A successful operation transferred every layer
+        if phase == ModelPhase::Success {
+            for layer in &mut self.layers {
+                layer.completed = layer.total;
+            }
+        }
+
+        self.progress(phase)
+    }
+
+    fn progress(&self, phase: ModelPhase) -> ModelProgress {
+        ModelProgress {
+            phase,
+            layers: self.layers.clone(),
+            total: self.layers.iter().map(|l| l.total).sum(),
+            completed: self.layers.iter().map(|l| l.completed).sum(),
+        }
+    }
+}
+
+/// Turns a stream of status updates, such as the one of [`crate::Ollama::pull_model_stream`],
+/// into a stream of the overall progress.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ollama_rs::{models::progress::track_progress, Ollama};
+/// use tokio_stream::StreamExt;
+///
+/// # async fn run() -> ollama_rs::error::Result<()> {
+/// let ollama = Ollama::default();
+///
+/// let stream = ollama.pull_model_stream("llama2:latest", false).await?;
+/// let mut progress = track_progress(stream);
+///
+/// while let Some(progress) = progress.next().await {
+///     let progress = progress?;
+///     println!("{:?}: {:.1}%", progress.phase, progress.percentage().unwrap_or(0.0));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+#[cfg(feature = "stream")]
+pub fn track_progress<S, T>(stream: S) -> ModelProgressStream
+where
+    S: tokio_stream::Stream<Item = crate::error::Result<T>> + Send + 'static,
+    T: ModelStatus,
+{
+    use tokio_stream::StreamExt;
+
+    let mut tracker = ProgressTracker::new();
+
+    Box::pin(stream.map(move |status| status.map(|status| tracker.update(&status))))
+}
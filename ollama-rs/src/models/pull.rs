@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{error::OllamaError, Ollama};
+use crate::Ollama;
 
 /// A stream of `PullModelStatus` objects.
 #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
@@ -17,50 +17,22 @@ impl Ollama {
     /// - `allow_insecure` - Allow insecure connections to the library. Only use this if you are pulling from your own library during development.
     pub async fn pull_model_stream(
         &self,
-        model_name: String,
+        model_name: impl Into<String>,
         allow_insecure: bool,
     ) -> crate::error::Result<PullModelStatusStream> {
-        use tokio_stream::StreamExt;
-
-        use crate::error::{InternalOllamaError, OllamaError};
-
         let request = PullModelRequest {
-            model_name,
+            model_name: model_name.into(),
             allow_insecure,
             stream: true,
         };
 
         let url = format!("{}api/pull", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
-
-        let res = builder.body(serialized).send().await?;
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(res.text().await?));
-        }
+        let res = self.send_stream_request(builder, true).await?;
 
-        let stream = Box::new(res.bytes_stream().map(|res| match res {
-            Ok(bytes) => {
-                let res = serde_json::from_slice::<PullModelStatus>(&bytes);
-                match res {
-                    Ok(res) => Ok(res),
-                    Err(e) => {
-                        let err = serde_json::from_slice::<InternalOllamaError>(&bytes);
-                        match err {
-                            Ok(err) => Err(OllamaError::InternalError(err)),
-                            Err(_) => Err(e.into()),
-                        }
-                    }
-                }
-            }
-            Err(e) => Err(e.into()),
-        }));
-
-        Ok(std::pin::Pin::from(stream))
+        Ok(self.ndjson_stream(res))
     }
 
     /// Pull a model with a single response, only the final status will be returned.
@@ -68,30 +40,21 @@ impl Ollama {
     /// - `allow_insecure` - Allow insecure connections to the library. Only use this if you are pulling from your own library during development.
     pub async fn pull_model(
         &self,
-        model_name: String,
+        model_name: impl Into<String>,
         allow_insecure: bool,
     ) -> crate::error::Result<PullModelStatus> {
         let request = PullModelRequest {
-            model_name,
+            model_name: model_name.into(),
             allow_insecure,
             stream: false,
         };
 
         let url = format!("{}api/pull", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
-
-        let res = builder.body(serialized).send().await?;
-
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(res.text().await?));
-        }
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        let res = res.bytes().await?;
-        let res = serde_json::from_slice::<PullModelStatus>(&res)?;
+        let bytes = self.send_request(builder, true).await?;
+        let res = serde_json::from_slice::<PullModelStatus>(&bytes)?;
 
         Ok(res)
     }
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{error::OllamaError, Ollama};
+use crate::Ollama;
 
 /// A stream of `PushModelStatus` objects.
 #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
@@ -18,53 +18,22 @@ impl Ollama {
     /// - `allow_insecure` - Allow insecure connections to the library. Only use this if you are pushing to your library during development.
     pub async fn push_model_stream(
         &self,
-        model_name: String,
+        model_name: impl Into<String>,
         allow_insecure: bool,
     ) -> crate::error::Result<PushModelStatusStream> {
-        use crate::error::OllamaError;
-        use tokio_stream::StreamExt;
-
         let request = PushModelRequest {
-            model_name,
+            model_name: model_name.into(),
             allow_insecure,
             stream: true,
         };
 
         let url = format!("{}api/push", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
-
-        let res = builder.body(serialized).send().await?;
-
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(res.text().await?));
-        }
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        let stream = Box::new(res.bytes_stream().map(|res| match res {
-            Ok(bytes) => {
-                let res = serde_json::from_slice::<PushModelStatus>(&bytes);
-                match res {
-                    Ok(res) => Ok(res),
-                    Err(e) => {
-                        let err =
-                            serde_json::from_slice::<crate::error::InternalOllamaError>(&bytes);
-                        match err {
-                            Ok(err) => Err(OllamaError::InternalError(err)),
-                            Err(_) => Err(e.into()),
-                        }
-                    }
-                }
-            }
-            Err(e) => Err(OllamaError::Other(format!(
-                "Failed to read response: {}",
-                e
-            ))),
-        }));
+        let res = self.send_stream_request(builder, true).await?;
 
-        Ok(std::pin::Pin::from(stream))
+        Ok(self.ndjson_stream(res))
     }
 
     /// Upload a model to a model library. Requires registering for ollama.ai and adding a public key first.
@@ -73,30 +42,21 @@ impl Ollama {
     /// - `allow_insecure` - Allow insecure connections to the library. Only use this if you are pushing to your library during development.
     pub async fn push_model(
         &self,
-        model_name: String,
+        model_name: impl Into<String>,
         allow_insecure: bool,
     ) -> crate::error::Result<PushModelStatus> {
         let request = PushModelRequest {
-            model_name,
+            model_name: model_name.into(),
             allow_insecure,
             stream: false,
         };
 
         let url = format!("{}api/push", self.url_str());
         let serialized = serde_json::to_string(&request)?;
-        let builder = self.reqwest_client.post(url);
-
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
-
-        let res = builder.body(serialized).send().await?;
-
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(res.text().await?));
-        }
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        let res = res.bytes().await?;
-        let res = serde_json::from_slice::<PushModelStatus>(&res)?;
+        let bytes = self.send_request(builder, true).await?;
+        let res = serde_json::from_slice::<PushModelStatus>(&bytes)?;
 
         Ok(res)
     }
@@ -119,4 +79,5 @@ pub struct PushModelStatus {
     pub message: String,
     pub digest: Option<String>,
     pub total: Option<u64>,
+    pub completed: Option<u64>,
 }
@@ -1,35 +1,71 @@
 use serde::Serialize;
 
-use crate::{error::OllamaError, Ollama};
+use crate::Ollama;
 
-use super::ModelInfo;
+use super::{ModelInfo, ModelName};
 
 impl Ollama {
     /// Show details about a model including modelfile, template, parameters, license, and system prompt.
-    pub async fn show_model_info(&self, model_name: String) -> crate::error::Result<ModelInfo> {
+    ///
+    /// Takes a model name, or a [`ShowModelInfoRequest`] to get the verbose information.
+    pub async fn show_model_info(
+        &self,
+        request: impl Into<ShowModelInfoRequest>,
+    ) -> crate::error::Result<ModelInfo> {
         let url = format!("{}api/show", self.url_str());
-        let serialized = serde_json::to_string(&ModelInfoRequest { model_name })?;
-        let builder = self.reqwest_client.post(url);
+        let serialized = serde_json::to_string(&request.into())?;
+        let builder = self.reqwest_client.post(url).body(serialized);
 
-        #[cfg(feature = "headers")]
-        let builder = builder.headers(self.request_headers.clone());
+        let bytes = self.send_request(builder, true).await?;
+        let res = serde_json::from_slice::<ModelInfo>(&bytes)?;
 
-        let res = builder.body(serialized).send().await?;
+        Ok(res)
+    }
+}
+
+/// A show model info request to Ollama.
+#[derive(Debug, Clone, Serialize)]
+pub struct ShowModelInfoRequest {
+    #[serde(rename = "name")]
+    pub(crate) model_name: String,
+    pub(crate) verbose: bool,
+}
 
-        if !res.status().is_success() {
-            return Err(OllamaError::Other(res.text().await?));
+impl ShowModelInfoRequest {
+    pub fn new(model_name: impl Into<String>) -> Self {
+        Self {
+            model_name: model_name.into(),
+            verbose: false,
         }
+    }
+
+    /// Also return the full tokenizer data and the list of the tensors of the model, which can be large.
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+}
 
-        let res = res.bytes().await?;
-        let res = serde_json::from_slice::<ModelInfo>(&res)?;
+impl From<String> for ShowModelInfoRequest {
+    fn from(model_name: String) -> Self {
+        Self::new(model_name)
+    }
+}
 
-        Ok(res)
+impl From<&str> for ShowModelInfoRequest {
+    fn from(model_name: &str) -> Self {
+        Self::new(model_name)
     }
 }
 
-/// A show model info request to Ollama.
-#[derive(Serialize)]
-struct ModelInfoRequest {
-    #[serde(rename = "name")]
-    model_name: String,
+impl From<ModelName> for ShowModelInfoRequest {
+    fn from(model_name: ModelName) -> Self {
+        Self::new(model_name)
+    }
+}
+
+impl From<&ModelName> for ShowModelInfoRequest {
+    fn from(model_name: &ModelName) -> Self {
+        Self::new(model_name)
+    }
 }
@@ -0,0 +1,278 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use bytes::Bytes;
+
+use crate::{
+    error::{ApiError, ApiErrorKind, OllamaError},
+    Ollama,
+};
+
+/// Controls how requests are retried when they fail with a transient error.
+///
+/// The default policy makes a single attempt, so nothing is retried unless
+/// `max_attempts` is raised.
+///
+/// Requests are only retried before any response data has been handed back to the caller:
+/// a streaming call can be retried while the stream is being established, but not once
+/// items have been yielded. Requests that are not idempotent (e.g. creating or deleting a
+/// model) are only retried when the server certainly did not process them, unless
+/// `retry_non_idempotent` is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use ollama_rs::{retry::RetryPolicy, Ollama};
+///
+/// let mut ollama = Ollama::default();
+/// ollama.set_retry_policy(
+///     RetryPolicy::default()
+///         .max_attempts(5)
+///         .initial_backoff(Duration::from_millis(500)),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    backoff_multiplier: f64,
+    jitter: bool,
+    retryable_kinds: Vec<ApiErrorKind>,
+    retry_connection_errors: bool,
+    retry_timeouts: bool,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(30),
+            backoff_multiplier: 2.0,
+            jitter: true,
+            retryable_kinds: vec![ApiErrorKind::ServerOverloaded, ApiErrorKind::ServerError],
+            retry_connection_errors: true,
+            retry_timeouts: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// The maximum number of attempts, including the first one. (Default: 1)
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The delay before the first retry. (Default: 250ms)
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// The upper bound for the delay between two attempts. (Default: 30s)
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// The factor the delay is multiplied by after each attempt. (Default: 2.0)
+    pub fn backoff_multiplier(mut self, backoff_multiplier: f64) -> Self {
+        self.backoff_multiplier = backoff_multiplier.max(1.0);
+        self
+    }
+
+    /// Randomize each delay between half and all of its computed value, so that many clients
+    /// do not retry in lockstep. (Default: true)
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The kinds of API errors that are retried. (Default: `ServerOverloaded` and `ServerError`)
+    pub fn retryable_kinds(mut self, retryable_kinds: Vec<ApiErrorKind>) -> Self {
+        self.retryable_kinds = retryable_kinds;
+        self
+    }
+
+    /// Retry when the connection to the server could not be established, e.g. while the server is restarting. (Default: true)
+    pub fn retry_connection_errors(mut self, retry_connection_errors: bool) -> Self {
+        self.retry_connection_errors = retry_connection_errors;
+        self
+    }
+
+    /// Retry when the request timed out. (Default: true)
+    pub fn retry_timeouts(mut self, retry_timeouts: bool) -> Self {
+        self.retry_timeouts = retry_timeouts;
+        self
+    }
+
+    /// Also retry non-idempotent requests after failures that may have happened once the server started processing them. (Default: false)
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    /// Returns the delay to wait before the next attempt, or `None` if the error should be returned.
+    ///
+    /// `attempt` is the number of the attempt that just failed, starting at 1.
+    pub fn next_delay(
+        &self,
+        attempt: u32,
+        error: &OllamaError,
+        idempotent: bool,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable(error, idempotent) {
+            return None;
+        }
+
+        let exponent = i32::try_from(attempt - 1).unwrap_or(i32::MAX);
+        let delay = self
+            .initial_backoff
+            .mul_f64(self.backoff_multiplier.powi(exponent))
+            .min(self.max_backoff);
+
+        let delay = if self.jitter {
+            delay.mul_f64(0.5 + 0.5 * random_fraction())
+        } else {
+            delay
+        };
+
+        // Honor the server's hint when it asks for a longer wait
+        match error {
+            OllamaError::ApiError(ApiError {
+                retry_after: Some(retry_after),
+                ..
+            }) => Some(delay.max(*retry_after)),
+            _ => Some(delay),
+        }
+    }
+
+    fn is_retryable(&self, error: &OllamaError, idempotent: bool) -> bool {
+        match error {
+            // The request never reached the server, so it is always safe to send it again
+            OllamaError::ReqwestError(e) if e.is_connect() => self.retry_connection_errors,
+            OllamaError::ReqwestError(e) if e.is_timeout() => {
+                self.retry_timeouts && (idempotent || self.retry_non_idempotent)
+            }
+            OllamaError::ApiError(e) => {
+                self.retryable_kinds.contains(&e.kind)
+                    && (idempotent
+                        || self.retry_non_idempotent
+                        || e.kind == ApiErrorKind::ServerOverloaded)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Returns a pseudo-random number in `[0, 1)`, good enough to spread out retries.
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+impl Ollama {
+    /// Sets the retry policy used by every request method.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Returns the retry policy used by every request method.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Sends a request, retrying according to the retry policy, and returns the body of the response.
+    /// Non-success responses are turned into [`ApiError`]s.
+    ///
+    /// `idempotent` tells whether sending the request twice has the same effect as sending it once.
+    pub(crate) async fn send_request(
+        &self,
+        builder: reqwest::RequestBuilder,
+        idempotent: bool,
+    ) -> crate::error::Result<Bytes> {
+        let builder = match self.request_timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        };
+
+        self.cancellable(async {
+            let res = self.send_with_retry(builder, idempotent).await?;
+            Ok(res.bytes().await?)
+        })
+        .await
+    }
+
+    /// Same as [`Ollama::send_request`], for requests whose response body is streamed.
+    ///
+    /// The request timeout is not applied, as it would bound the whole stream.
+    #[cfg(feature = "stream")]
+    pub(crate) async fn send_stream_request(
+        &self,
+        builder: reqwest::RequestBuilder,
+        idempotent: bool,
+    ) -> crate::error::Result<reqwest::Response> {
+        self.cancellable(self.send_with_retry(builder, idempotent))
+            .await
+    }
+
+    async fn send_with_retry(
+        &self,
+        builder: reqwest::RequestBuilder,
+        idempotent: bool,
+    ) -> crate::error::Result<reqwest::Response> {
+        #[cfg(feature = "headers")]
+        let builder = builder.headers(self.request_headers.clone());
+
+        let mut attempt = 1;
+        loop {
+            // Requests with a streaming body cannot be cloned, and therefore cannot be retried
+            let Some(request) = builder.try_clone() else {
+                return check_response(builder.send().await?).await;
+            };
+
+            let error = match request.send().await {
+                Ok(res) => match check_response(res).await {
+                    Ok(res) => return Ok(res),
+                    Err(e) => e,
+                },
+                Err(e) => OllamaError::from(e),
+            };
+
+            match self.retry_policy.next_delay(attempt, &error, idempotent) {
+                Some(delay) => {
+                    log::debug!(
+                        "Request failed on attempt {}, retrying in {:?}: {}",
+                        attempt,
+                        delay,
+                        error
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return Err(error),
+            }
+        }
+    }
+}
+
+async fn check_response(res: reqwest::Response) -> crate::error::Result<reqwest::Response> {
+    if res.status().is_success() {
+        Ok(res)
+    } else {
+        Err(ApiError::from_response(res).await.into())
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use async_stream::stream;
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::{
+    error::{InternalOllamaError, OllamaError},
+    Ollama,
+};
+
+/// A stream of items decoded from a newline-delimited JSON response.
+pub(crate) type NdjsonStream<T> =
+    std::pin::Pin<Box<dyn Stream<Item = crate::error::Result<T>> + Send>>;
+
+impl Ollama {
+    /// Decodes the body of a streamed response as newline-delimited JSON, yielding one item per line.
+    ///
+    /// Lines may be split across any number of chunks, including in the middle of a
+    /// multi-byte character. Error objects sent by the server in the middle of the stream
+    /// are yielded as [`OllamaError::InternalError`].
+    pub(crate) fn ndjson_stream<T>(&self, res: reqwest::Response) -> NdjsonStream<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let body = self.body_stream(res);
+
+        let s = stream! {
+            let mut body = std::pin::pin!(body);
+            let mut decoder = NdjsonDecoder::default();
+
+            while let Some(chunk) = body.next().await {
+                match chunk {
+                    Ok(chunk) => {
+                        decoder.push(&chunk);
+                        while let Some(line) = decoder.next_line() {
+                            yield decode_line(&line);
+                        }
+                    }
+                    Err(e) => {
+                        yield Err(e);
+                        return;
+                    }
+                }
+            }
+
+            // The last line may not be terminated by a newline
+            if let Some(line) = decoder.finish() {
+                yield decode_line(&line);
+            }
+        };
+
+        Box::pin(s)
+    }
+
+    /// Returns the body of a streamed response as a stream of chunks.
+    ///
+    /// If a stream idle timeout is configured, the stream ends with
+    /// [`OllamaError::StreamIdleTimeout`] when no chunk arrives in time.
+    /// If the client has a cancellation token, the stream ends with [`OllamaError::Cancelled`]
+    /// as soon as it is cancelled, which also aborts the HTTP request.
+    pub(crate) fn body_stream(
+        &self,
+        res: reqwest::Response,
+    ) -> impl Stream<Item = crate::error::Result<Bytes>> + Send {
+        let idle_timeout = self.stream_idle_timeout;
+        let cancellation = self.cancellation.clone().unwrap_or_default();
+
+        stream! {
+            let mut chunks = std::pin::pin!(res.bytes_stream());
+
+            loop {
+                let next = tokio::select! {
+                    biased;
+                    _ = cancellation.cancelled() => Err(OllamaError::Cancelled),
+                    next = next_chunk(&mut chunks, idle_timeout) => next,
+                };
+
+                match next {
+                    Ok(Some(chunk)) => yield chunk.map_err(OllamaError::from),
+                    Ok(None) => break,
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn next_chunk<S>(
+    chunks: &mut S,
+    idle_timeout: Option<Duration>,
+) -> crate::error::Result<Option<reqwest::Result<Bytes>>>
+where
+    S: Stream<Item = reqwest::Result<Bytes>> + Unpin,
+{
+    match idle_timeout {
+        Some(idle_timeout) => tokio::time::timeout(idle_timeout, chunks.next())
+            .await
+            .map_err(|_| OllamaError::StreamIdleTimeout(idle_timeout)),
+        None => Ok(chunks.next().await),
+    }
+}
+
+/// Splits a stream of bytes into lines, buffering incomplete ones.
+///
+/// Lines are only split on `\n`, which never appears inside a multi-byte UTF-8 sequence,
+/// so complete lines are always valid UTF-8 if the input is.
+#[derive(Debug, Default)]
+struct NdjsonDecoder {
+    buffer: Vec<u8>,
+}
+
+impl NdjsonDecoder {
+    fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Returns the next complete, non-empty line.
+    fn next_line(&mut self) -> Option<Vec<u8>> {
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let mut line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            line.pop();
+
+            if !line.trim_ascii().is_empty() {
+                return Some(line);
+            }
+        }
+
+        None
+    }
+
+    /// Returns what remains in the buffer once the stream is over, if anything.
+    fn finish(self) -> Option<Vec<u8>> {
+        (!self.buffer.trim_ascii().is_empty()).then_some(self.buffer)
+    }
+}
+
+fn decode_line<T: DeserializeOwned>(line: &[u8]) -> crate::error::Result<T> {
+    match serde_json::from_slice::<T>(line) {
+        Ok(item) => Ok(item),
+        Err(e) => match serde_json::from_slice::<InternalOllamaError>(line) {
+            Ok(err) => Err(OllamaError::InternalError(err)),
+            Err(_) => Err(e.into()),
+        },
+    }
+}
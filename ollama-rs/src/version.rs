@@ -0,0 +1,248 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::{error::OllamaError, Ollama};
+
+/// The version of an Ollama server, following semantic versioning.
+///
+/// # Examples
+///
+/// ```
+/// use ollama_rs::version::OllamaVersion;
+///
+/// let version: OllamaVersion = "0.5.7-rc1".parse().unwrap();
+///
+/// assert!(version >= OllamaVersion::new(0, 5, 0));
+/// assert!(version < OllamaVersion::new(0, 5, 7));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OllamaVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// The pre-release identifier, e.g. `rc1` in `0.5.7-rc1`.
+    pub pre: Option<String>,
+}
+
+impl OllamaVersion {
+    /// Creates a release version.
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: None,
+        }
+    }
+
+    /// Whether this is the version reported by development builds of Ollama (`0.0.0`),
+    /// which cannot be compared to releases.
+    pub fn is_dev_build(&self) -> bool {
+        self.major == 0 && self.minor == 0 && self.patch == 0
+    }
+}
+
+impl FromStr for OllamaVersion {
+    type Err = OllamaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || OllamaError::Other(format!("Invalid Ollama version: {:?}", s));
+
+        let version = s.trim().trim_start_matches('v');
+        // Build metadata is ignored
+        let version = version.split_once('+').map_or(version, |(v, _)| v);
+        let (version, pre) = match version.split_once('-') {
+            Some((version, pre)) => (version, Some(pre.to_string())),
+            None => (version, None),
+        };
+
+        let mut parts = version.split('.').map(|p| p.parse::<u64>());
+        let (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+}
+
+impl fmt::Display for OllamaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for OllamaVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                // A pre-release comes before the release
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => cmp_pre_release(a, b),
+            })
+    }
+}
+
+/// Compares pre-release identifiers as semantic versioning does: dot-separated identifiers one by
+/// one, numeric ones numerically and before alphanumeric ones, and a shorter list first.
+///
+/// Unlike semantic versioning, the number ending an alphanumeric identifier is also compared
+/// numerically, so that `rc2` comes before `rc10`.
+fn cmp_pre_release(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match cmp_identifier(a, b) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            },
+        }
+    }
+}
+
+fn cmp_identifier(a: &str, b: &str) -> Ordering {
+    let is_numeric = |s: &str| !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit());
+
+    match (is_numeric(a), is_numeric(b)) {
+        (true, true) => split_number(a).1.cmp(&split_number(b).1),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => split_number(a).cmp(&split_number(b)),
+    }
+    .then_with(|| a.cmp(b))
+}
+
+/// Splits `rc10` into `rc` and 10.
+fn split_number(identifier: &str) -> (&str, Option<u64>) {
+    let prefix = identifier.trim_end_matches(|c: char| c.is_ascii_digit());
+    (prefix, identifier[prefix.len()..].parse().ok())
+}
+
+impl PartialOrd for OllamaVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A feature of the Ollama API that is only available from a given server version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// Tool calling in chat requests.
+    Tools,
+    /// Tool calling in streamed chat requests.
+    StreamingToolCalls,
+    /// Responses following a JSON schema, see [`crate::generation::parameters::FormatType::StructuredJson`].
+    StructuredOutputs,
+    /// Separate reasoning output, see [`crate::generation::parameters::Think`].
+    Thinking,
+}
+
+impl Capability {
+    /// The first version of Ollama supporting this capability.
+    pub fn min_version(&self) -> OllamaVersion {
+        match self {
+            Capability::Tools => OllamaVersion::new(0, 3, 0),
+            Capability::StreamingToolCalls => OllamaVersion::new(0, 8, 0),
+            Capability::StructuredOutputs => OllamaVersion::new(0, 5, 0),
+            Capability::Thinking => OllamaVersion::new(0, 9, 0),
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Capability::Tools => f.write_str("Tool calling"),
+            Capability::StreamingToolCalls => f.write_str("Streaming tool calls"),
+            Capability::StructuredOutputs => f.write_str("Structured outputs"),
+            Capability::Thinking => f.write_str("Thinking"),
+        }
+    }
+}
+
+impl Ollama {
+    /// Returns the version of the Ollama server.
+    pub async fn version(&self) -> crate::error::Result<OllamaVersion> {
+        let url = format!("{}api/version", self.url_str());
+        let builder = self.reqwest_client.get(url);
+
+        let bytes = self.send_request(builder, true).await?;
+        let res = serde_json::from_slice::<VersionResponse>(&bytes)?;
+
+        res.version.parse()
+    }
+
+    /// Returns whether the Ollama server supports `capability`.
+    ///
+    /// Development builds of Ollama are assumed to support everything.
+    pub async fn supports(&self, capability: Capability) -> crate::error::Result<bool> {
+        let version = self.server_version().await?;
+
+        Ok(version.is_dev_build() || version >= capability.min_version())
+    }
+
+    /// Whether requests check that the server supports the features they use before being sent.
+    ///
+    /// When enabled, the server version is fetched once and cached, and a request using an
+    /// unsupported feature fails with [`OllamaError::UnsupportedCapability`]. (Default: false)
+    pub fn set_check_capabilities(&mut self, check_capabilities: bool) {
+        self.check_capabilities = check_capabilities;
+    }
+
+    /// Fails if capability checks are enabled and the server does not support one of `capabilities`.
+    pub(crate) async fn check_capabilities(
+        &self,
+        capabilities: &[Capability],
+    ) -> crate::error::Result<()> {
+        if !self.check_capabilities || capabilities.is_empty() {
+            return Ok(());
+        }
+
+        let version = self.server_version().await?;
+        if version.is_dev_build() {
+            return Ok(());
+        }
+
+        match capabilities.iter().find(|c| version < c.min_version()) {
+            Some(capability) => Err(OllamaError::UnsupportedCapability {
+                capability: *capability,
+                server_version: version,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the version of the server, only requesting it the first time.
+    async fn server_version(&self) -> crate::error::Result<OllamaVersion> {
+        if let Some(version) = self.server_version.get() {
+            return Ok(version.clone());
+        }
+
+        let version = self.version().await?;
+        Ok(self.server_version.get_or_init(|| version).clone())
+    }
+}
+
+/// A response from Ollama containing its version.
+#[derive(Debug, Clone, Deserialize)]
+struct VersionResponse {
+    version: String,
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use std::{
+    io::Cursor,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ollama_rs::Ollama;
+
+use common::Response;
+
+const CONTENT: &[u8] = b"hello";
+const DIGEST: &str = "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+/// Starts a server recording the request line and body of each request.
+/// `HEAD` requests are answered with `head_status`, others with `201 Created` after `upload_delay`.
+async fn serve_blobs(
+    head_status: &'static str,
+    upload_delay: Duration,
+) -> (Ollama, Arc<Mutex<Vec<(String, String)>>>) {
+    let requests = Arc::new(Mutex::new(vec![]));
+
+    let recorded = requests.clone();
+    let port = common::serve(move |request| {
+        let res = if request.method() == "HEAD" {
+            Response::status(head_status)
+        } else {
+            Response::status("201 Created").delay(upload_delay)
+        };
+        recorded.lock().unwrap().push((request.line, request.body));
+        res
+    })
+    .await;
+
+    (Ollama::new("http://127.0.0.1", port), requests)
+}
+
+#[tokio::test]
+async fn test_push_blob() {
+    let (ollama, requests) = serve_blobs("404 Not Found", Duration::ZERO).await;
+
+    let digest = ollama
+        .push_blob_reader(Cursor::new(CONTENT.to_vec()))
+        .await
+        .unwrap();
+    assert_eq!(digest, DIGEST);
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[0].0,
+        format!("HEAD /api/blobs/{} HTTP/1.1", DIGEST)
+    );
+    assert_eq!(
+        requests[1].0,
+        format!("POST /api/blobs/{} HTTP/1.1", DIGEST)
+    );
+    assert!(requests[1].1.contains("hello"));
+}
+
+#[tokio::test]
+async fn test_push_blob_without_request_timeout() {
+    let (ollama, _) = serve_blobs("404 Not Found", Duration::from_millis(300)).await;
+    let ollama = Ollama::builder()
+        .host(ollama.url().as_str())
+        .request_timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    // The upload takes longer than the request timeout
+    let digest = ollama
+        .push_blob_reader(Cursor::new(CONTENT.to_vec()))
+        .await
+        .unwrap();
+    assert_eq!(digest, DIGEST);
+}
+
+#[tokio::test]
+async fn test_push_existing_blob() {
+    let (ollama, requests) = serve_blobs("200 OK", Duration::ZERO).await;
+
+    let path = std::env::temp_dir().join("ollama-rs-test-blob");
+    tokio::fs::write(&path, CONTENT).await.unwrap();
+
+    let digest = ollama.push_blob(&path).await.unwrap();
+    assert_eq!(digest, DIGEST);
+
+    // Only the existence check is sent
+    assert_eq!(requests.lock().unwrap().len(), 1);
+    assert!(ollama.blob_exists(DIGEST).await.unwrap());
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_blob_does_not_exist() {
+    let ollama = Ollama::default();
+
+    let exists = ollama
+        .blob_exists("sha256:0000000000000000000000000000000000000000000000000000000000000000")
+        .await
+        .unwrap();
+
+    assert!(!exists);
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use ollama_rs::{
+    builder::parse_ollama_host,
+    error::OllamaError,
+    generation::{completion::request::GenerationRequest, parameters::KeepAlive},
+    models::ModelOptions,
+    Ollama,
+};
+
+#[test]
+fn test_builder_defaults() {
+    let ollama = Ollama::builder().build().unwrap();
+
+    assert_eq!(ollama.url_str(), Ollama::default().url_str());
+}
+
+#[test]
+fn test_builder_base_path() {
+    let ollama = Ollama::builder()
+        .host("http://localhost")
+        .port(8080)
+        .base_path("ollama")
+        .build()
+        .unwrap();
+
+    assert_eq!(ollama.url_str(), "http://localhost:8080/ollama/");
+
+    // A path given with the host is kept
+    let ollama = Ollama::builder()
+        .host("https://example.com/proxy/ollama")
+        .build()
+        .unwrap();
+
+    assert_eq!(ollama.url_str(), "https://example.com/proxy/ollama/");
+}
+
+#[test]
+fn test_builder_invalid_host() {
+    let res = Ollama::builder().host("not a url").build();
+
+    assert!(matches!(res, Err(OllamaError::UrlError(_))));
+}
+
+#[tokio::test]
+async fn test_builder_generation() {
+    let ollama = Ollama::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .request_timeout(Duration::from_secs(120))
+        .stream_idle_timeout(Duration::from_secs(30))
+        .default_options(ModelOptions::default().temperature(0.0))
+        .default_keep_alive(KeepAlive::UnloadOnCompletion)
+        .user_agent("ollama-rs-tests")
+        .build()
+        .unwrap();
+
+    let res = ollama
+        .generate(GenerationRequest::new(
+            "llama2:latest".to_string(),
+            "Why is the sky blue?",
+        ))
+        .await
+        .unwrap();
+
+    dbg!(res);
+}
+
+#[test]
+fn test_parse_ollama_host() {
+    let cases = [
+        ("", "http://127.0.0.1:11434/"),
+        ("0.0.0.0", "http://0.0.0.0:11434/"),
+        (":11434", "http://127.0.0.1:11434/"),
+        ("example.com", "http://example.com:11434/"),
+        ("example.com:1234", "http://example.com:1234/"),
+        ("http://example.com", "http://example.com/"),
+        ("https://example.com", "https://example.com/"),
+        (
+            "https://example.com:1234/ollama",
+            "https://example.com:1234/ollama",
+        ),
+        ("  \"example.com\"  ", "http://example.com:11434/"),
+        ("example.com:not-a-port", "http://example.com:11434/"),
+        ("[::1]:1234", "http://[::1]:1234/"),
+        ("::1", "http://[::1]:11434/"),
+    ];
+
+    for (value, expected) in cases {
+        let url = parse_ollama_host(value).unwrap();
+        assert_eq!(url.as_str(), expected, "{:?}", value);
+    }
+}
+
+#[test]
+fn test_from_env_builder() {
+    // Only this test touches the environment of the test process
+    std::env::set_var("OLLAMA_HOST", "https://example.com/ollama");
+
+    let ollama = Ollama::from_env().unwrap();
+
+    assert_eq!(ollama.url_str(), "https://example.com/ollama/");
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ollama_rs::{
+    error::OllamaError,
+    models::bulk_pull::{BulkPullEvent, BulkPullRequest, PullOutcome, PullTarget},
+    Ollama,
+};
+use tokio_stream::StreamExt;
+
+use common::Response;
+
+const TAGS: &str = r#"{"models":[
+    {"name":"llama2:latest","modified_at":"2024-01-01T00:00:00Z","size":1,"digest":"78e26419b446"},
+    {"name":"mistral:latest","modified_at":"2024-01-01T00:00:00Z","size":1,"digest":"61e88e884507"}
+]}"#;
+
+/// Starts a fake registry where the first pull of `phi3` and every pull of `flaky` are interrupted,
+/// and `unknown` does not exist.
+/// Returns how many times each model was pulled.
+async fn serve_registry() -> (Ollama, Arc<Mutex<HashMap<String, u32>>>) {
+    let pulls = Arc::new(Mutex::new(HashMap::new()));
+
+    let counts = pulls.clone();
+    let port = common::serve(move |request| {
+        if request.line.starts_with("GET /api/tags") {
+            return Response::ok(TAGS);
+        }
+
+        let model = request.json()["name"].as_str().unwrap().to_string();
+        let attempt = {
+            let mut counts = counts.lock().unwrap();
+            let count = counts.entry(model.clone()).or_insert(0);
+            *count += 1;
+            *count
+        };
+
+        Response::ok(match (model.as_str(), attempt) {
+            ("unknown", _) => concat!(
+                r#"{"status":"pulling manifest"}"#,
+                "\n",
+                r#"{"error":"pull model manifest: file does not exist"}"#,
+                "\n"
+            ),
+            ("phi3", 1) | ("flaky", _) => concat!(
+                r#"{"status":"pulling manifest"}"#,
+                "\n",
+                r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a07","total":100,"completed":40}"#,
+                "\n"
+            ),
+            _ => concat!(
+                r#"{"status":"pulling manifest"}"#,
+                "\n",
+                r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a07","total":100,"completed":100}"#,
+                "\n",
+                r#"{"status":"success"}"#,
+                "\n"
+            ),
+        })
+    })
+    .await;
+
+    (Ollama::new("http://127.0.0.1", port), pulls)
+}
+
+#[tokio::test]
+async fn test_pull_models() {
+    let (ollama, pulls) = serve_registry().await;
+
+    let request = BulkPullRequest::new([
+        // Present locally
+        PullTarget::new("llama2"),
+        // Present locally, but with another digest
+        PullTarget::new("mistral").digest("sha256:0000"),
+        PullTarget::new("phi3"),
+        PullTarget::new("unknown"),
+    ])
+    .concurrency(2)
+    .retry_delay(Duration::from_millis(10));
+
+    let mut events = ollama.pull_models_stream(request).await.unwrap();
+    let mut outcomes = HashMap::new();
+    let mut retries = 0;
+    let mut last_progress = HashMap::new();
+    while let Some(event) = events.next().await {
+        match event {
+            BulkPullEvent::Progress { model, progress } => {
+                last_progress.insert(model, progress);
+            }
+            BulkPullEvent::Retrying { model, .. } => {
+                assert_eq!(model, "phi3");
+                retries += 1;
+            }
+            BulkPullEvent::Skipped { model } => {
+                outcomes.insert(model, PullOutcome::Skipped);
+            }
+            BulkPullEvent::Completed { model } => {
+                outcomes.insert(model, PullOutcome::Completed);
+            }
+            BulkPullEvent::Failed { model, error } => {
+                outcomes.insert(model, PullOutcome::Failed(error));
+            }
+        }
+    }
+
+    assert!(matches!(outcomes["llama2"], PullOutcome::Skipped));
+    assert!(matches!(outcomes["mistral"], PullOutcome::Completed));
+    assert!(matches!(outcomes["phi3"], PullOutcome::Completed));
+    assert!(matches!(
+        outcomes["unknown"],
+        PullOutcome::Failed(OllamaError::InternalError(_))
+    ));
+
+    // The interrupted pull was restarted once, the missing model was not retried
+    assert_eq!(retries, 1);
+    let pulls = pulls.lock().unwrap().clone();
+    assert_eq!(pulls["phi3"], 2);
+    assert_eq!(pulls["unknown"], 1);
+    assert!(!pulls.contains_key("llama2"));
+
+    assert_eq!(last_progress["phi3"].percentage(), Some(100.0));
+}
+
+#[tokio::test]
+async fn test_pull_models_outcomes() {
+    let (ollama, _) = serve_registry().await;
+
+    let outcomes = ollama
+        .pull_models(BulkPullRequest::new(["llama2", "phi3"]).retry_delay(Duration::ZERO))
+        .await
+        .unwrap();
+
+    assert_eq!(outcomes.len(), 2);
+    assert!(outcomes
+        .iter()
+        .all(|(_, o)| matches!(o, PullOutcome::Skipped | PullOutcome::Completed)));
+}
+
+#[tokio::test]
+async fn test_drop_stream_stops_pulls() {
+    let (ollama, pulls) = serve_registry().await;
+
+    let request = BulkPullRequest::new(["flaky"])
+        .max_retries(1000)
+        .retry_delay(Duration::from_millis(10));
+
+    let mut events = ollama.pull_models_stream(request).await.unwrap();
+    while let Some(event) = events.next().await {
+        if matches!(event, BulkPullEvent::Retrying { .. }) {
+            break;
+        }
+    }
+    drop(events);
+
+    // No pull is restarted once the stream is dropped
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let count = pulls.lock().unwrap()["flaky"];
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(pulls.lock().unwrap()["flaky"], count);
+}
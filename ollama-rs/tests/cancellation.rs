@@ -0,0 +1,107 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ollama_rs::{
+    cancellation::CancellationToken,
+    error::OllamaError,
+    generation::chat::{request::ChatMessageRequest, ChatMessage},
+    Ollama,
+};
+use tokio_stream::StreamExt;
+
+use common::Response;
+
+#[tokio::test]
+async fn test_cancel_pending_request() {
+    // A server that never answers in time
+    let port = common::serve(|_| Response::status("200 OK").delay(Duration::from_secs(3600))).await;
+
+    let token = CancellationToken::new();
+    let ollama = Ollama::new("http://127.0.0.1", port).with_cancellation(token.clone());
+
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        token.cancel();
+    });
+
+    let res = ollama.list_local_models().await;
+
+    assert!(matches!(res, Err(OllamaError::Cancelled)));
+    assert!(ollama.is_cancelled());
+}
+
+fn chat_frame(content: &str, done: bool) -> Vec<u8> {
+    format!(
+        concat!(
+            r#"{{"model":"llama2:latest","created_at":"2023-08-04T08:52:19.385406455-07:00","message":{{"role":"assistant","content":"{}"}},"done":{}}}"#,
+            "\n"
+        ),
+        content, done
+    )
+    .into_bytes()
+}
+
+/// Starts a server streaming a chat answer one word every 100ms.
+async fn serve_slow_chat() -> u16 {
+    let frames = vec![
+        chat_frame("The", false),
+        chat_frame(" sky", false),
+        chat_frame(" is blue.", false),
+        chat_frame("", true),
+    ];
+    common::serve(move |_| Response::chunked(frames.clone(), Duration::from_millis(100))).await
+}
+
+/// Cancels a chat stream with history after its first token, returning the history.
+async fn cancel_chat_with_history_stream(commit_partial_on_cancel: bool) -> Vec<ChatMessage> {
+    let port = serve_slow_chat().await;
+    let token = CancellationToken::new();
+    let mut ollama = Ollama::new("http://127.0.0.1", port).with_cancellation(token.clone());
+    ollama.set_commit_partial_on_cancel(commit_partial_on_cancel);
+    let history = Arc::new(Mutex::new(vec![]));
+
+    let mut res = ollama
+        .send_chat_messages_with_history_stream(
+            history.clone(),
+            ChatMessageRequest::new(
+                "llama2:latest".to_string(),
+                vec![ChatMessage::user("Why is the sky blue?".to_string())],
+            ),
+        )
+        .await
+        .unwrap();
+
+    // Stop after the first token
+    let first = res.next().await.unwrap();
+    assert_eq!(first.unwrap().message.content, "The");
+    token.cancel();
+
+    assert!(matches!(
+        res.next().await.unwrap(),
+        Err(OllamaError::Cancelled)
+    ));
+    assert!(res.next().await.is_none());
+
+    let history = history.lock().unwrap().clone();
+    history
+}
+
+#[tokio::test]
+async fn test_cancel_chat_with_history_stream() {
+    let history = cancel_chat_with_history_stream(false).await;
+
+    // Only the user message, the partial answer is not committed
+    assert_eq!(history.len(), 1);
+}
+
+#[tokio::test]
+async fn test_commit_partial_answer_on_cancel() {
+    let history = cancel_chat_with_history_stream(true).await;
+
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].content, "The");
+}
@@ -13,7 +13,7 @@ async fn test_chat_history_accumulated() {
         .send_chat_messages_with_history(
             &mut history,
             ChatMessageRequest::new(
-                "granite-code:3b".into(),
+                "granite-code:3b",
                 vec![ChatMessage::new(
                     MessageRole::User,
                     "Why is the sky blue?".into(),
@@ -27,7 +27,7 @@ async fn test_chat_history_accumulated() {
         .send_chat_messages_with_history(
             &mut history,
             ChatMessageRequest::new(
-                "granite-code:3b".into(),
+                "granite-code:3b",
                 vec![ChatMessage::new(
                     MessageRole::User,
                     "But, why is the sky blue?".into()
@@ -9,9 +9,8 @@ async fn typical_c_code_main() {
     const C_COMPLETION: &str = "ain";
 
     let options = ModelOptions::default().seed(146);
-    let request =
-        GenerationRequest::new_with_suffix(CODE_MODEL.into(), C_PREFIX.into(), C_SUFFIX.into())
-            .options(options);
+    let request = GenerationRequest::new_with_suffix(CODE_MODEL, C_PREFIX.into(), C_SUFFIX.into())
+        .options(options);
 
     let ollama = Ollama::default();
     let res = ollama.generate(request).await.unwrap();
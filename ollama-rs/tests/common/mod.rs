@@ -0,0 +1,229 @@
+//! A minimal HTTP/1.1 server standing in for Ollama in the tests.
+
+// Each test crate only uses part of this module
+#![allow(dead_code)]
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// A request received by the server.
+#[derive(Debug, Clone)]
+pub struct Request {
+    /// The request line, e.g. `POST /api/chat HTTP/1.1`.
+    pub line: String,
+    /// The headers, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    /// The body, decoded if it was sent in chunks.
+    pub body: String,
+}
+
+impl Request {
+    pub fn method(&self) -> &str {
+        self.line.split(' ').next().unwrap_or_default()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).unwrap()
+    }
+}
+
+/// A response sent by the server.
+pub struct Response {
+    status: &'static str,
+    body: Body,
+    delay: Duration,
+}
+
+enum Body {
+    Full(Vec<u8>),
+    /// Each frame is sent as a separate HTTP chunk, `interval` apart.
+    Chunked {
+        frames: Vec<Vec<u8>>,
+        interval: Duration,
+    },
+}
+
+impl Response {
+    /// A `200 OK` response with `body`.
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status: "200 OK",
+            body: Body::Full(body.into()),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// An empty response with `status`, e.g. `404 Not Found`.
+    pub fn status(status: &'static str) -> Self {
+        Self {
+            status,
+            body: Body::Full(vec![]),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// A `200 OK` NDJSON response sending each of `frames` as a separate HTTP chunk.
+    pub fn chunked(frames: Vec<Vec<u8>>, interval: Duration) -> Self {
+        Self {
+            status: "200 OK",
+            body: Body::Chunked { frames, interval },
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Waits for `delay` before answering.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Starts a server answering each request with the response returned by `handler`.
+/// Returns the port it listens on.
+pub async fn serve<F>(handler: F) -> u16
+where
+    F: Fn(Request) -> Response + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handler = Arc::new(handler);
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                if let Some(request) = read_request(&mut socket).await {
+                    write_response(&mut socket, handler(request)).await;
+                }
+            });
+        }
+    });
+
+    port
+}
+
+async fn read_request(socket: &mut TcpStream) -> Option<Request> {
+    let mut data = vec![];
+    let head_len = loop {
+        if let Some(pos) = find(&data, b"\r\n\r\n") {
+            break pos;
+        }
+        if !read_more(socket, &mut data).await {
+            return None;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..head_len]).to_string();
+    let mut lines = head.split("\r\n");
+    let line = lines.next().unwrap().to_string();
+    let headers = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    let mut request = Request {
+        line,
+        headers,
+        body: String::new(),
+    };
+
+    let mut rest = data.split_off(head_len + 4);
+    let body = if let Some(len) = request.header("content-length") {
+        let len: usize = len.parse().unwrap();
+        while rest.len() < len && read_more(socket, &mut rest).await {}
+        rest
+    } else if request
+        .header("transfer-encoding")
+        .is_some_and(|e| e.eq_ignore_ascii_case("chunked"))
+    {
+        read_chunks(socket, rest).await
+    } else {
+        vec![]
+    };
+    request.body = String::from_utf8_lossy(&body).to_string();
+
+    Some(request)
+}
+
+/// Decodes a chunked body, `data` holding what was already read of it.
+async fn read_chunks(socket: &mut TcpStream, mut data: Vec<u8>) -> Vec<u8> {
+    let mut body = vec![];
+    loop {
+        if let Some(pos) = find(&data, b"\r\n") {
+            let size = String::from_utf8_lossy(&data[..pos]);
+            let size = size.split(';').next().unwrap().trim();
+            let size = usize::from_str_radix(size, 16).unwrap();
+            // The chunk and its trailing CRLF, or the final CRLF after the last chunk
+            let end = pos + 2 + size + 2;
+            if data.len() >= end {
+                if size == 0 {
+                    return body;
+                }
+                body.extend_from_slice(&data[pos + 2..end - 2]);
+                data.drain(..end);
+                continue;
+            }
+        }
+        if !read_more(socket, &mut data).await {
+            return body;
+        }
+    }
+}
+
+/// Appends what can be read from `socket` to `data`, returns false once the connection is closed.
+async fn read_more(socket: &mut TcpStream, data: &mut Vec<u8>) -> bool {
+    let mut buf = [0; 4096];
+    let n = socket.read(&mut buf).await.unwrap_or(0);
+    data.extend_from_slice(&buf[..n]);
+    n > 0
+}
+
+async fn write_response(socket: &mut TcpStream, response: Response) {
+    tokio::time::sleep(response.delay).await;
+
+    match response.body {
+        Body::Full(body) => {
+            let head = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                response.status,
+                body.len()
+            );
+            let _ = socket.write_all(head.as_bytes()).await;
+            let _ = socket.write_all(&body).await;
+        }
+        Body::Chunked { frames, interval } => {
+            let head = format!(
+                "HTTP/1.1 {}\r\n\
+                Content-Type: application/x-ndjson\r\n\
+                Transfer-Encoding: chunked\r\n\
+                Connection: close\r\n\r\n",
+                response.status
+            );
+            let _ = socket.write_all(head.as_bytes()).await;
+            for frame in frames {
+                let _ = socket
+                    .write_all(format!("{:x}\r\n", frame.len()).as_bytes())
+                    .await;
+                let _ = socket.write_all(&frame).await;
+                let _ = socket.write_all(b"\r\n").await;
+                let _ = socket.flush().await;
+                tokio::time::sleep(interval).await;
+            }
+            let _ = socket.write_all(b"0\r\n\r\n").await;
+        }
+    }
+}
+
+fn find(data: &[u8], needle: &[u8]) -> Option<usize> {
+    data.windows(needle.len()).position(|w| w == needle)
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use ollama_rs::{
+    coordinator::{Coordinator, CoordinatorEvent},
+    generation::{chat::ChatMessage, tools::Tool},
+    Ollama,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use tokio_stream::StreamExt;
+
+use common::Response;
+
+const TOOL_CALL_STREAM: &str = concat!(
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"Let me compute it. "},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"multiply","arguments":{"a":6,"b":7}}}]},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":""},"done":true,"total_duration":1,"prompt_eval_count":1,"prompt_eval_duration":1,"eval_count":1,"eval_duration":1}"#,
+    "\n",
+);
+
+const ANSWER_STREAM: &str = concat!(
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"It is "},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"42."},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":""},"done":true,"total_duration":2,"prompt_eval_count":10,"prompt_eval_duration":1,"eval_count":3,"eval_duration":1}"#,
+    "\n",
+);
+
+#[derive(Deserialize, JsonSchema)]
+struct Params {
+    a: i64,
+    b: i64,
+}
+
+struct Multiply;
+
+impl Tool for Multiply {
+    type Params = Params;
+
+    fn name() -> &'static str {
+        "multiply"
+    }
+
+    fn description() -> &'static str {
+        "Multiplies two numbers."
+    }
+
+    async fn call(
+        &mut self,
+        parameters: Self::Params,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        Ok((parameters.a * parameters.b).to_string())
+    }
+}
+
+/// Starts a server calling the `multiply` tool until it receives its result.
+/// Returns the bodies of the chat requests.
+async fn serve_chat() -> (Ollama, Arc<Mutex<Vec<serde_json::Value>>>) {
+    let requests = Arc::new(Mutex::new(vec![]));
+
+    let bodies = requests.clone();
+    let port = common::serve(move |request| {
+        let body = request.json();
+        let has_result = body["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|m| m["role"] == "tool");
+        bodies.lock().unwrap().push(body);
+
+        Response::ok(if has_result {
+            ANSWER_STREAM
+        } else {
+            TOOL_CALL_STREAM
+        })
+    })
+    .await;
+
+    (Ollama::new("http://127.0.0.1", port), requests)
+}
+
+#[tokio::test]
+async fn test_coordinator_chat_stream() {
+    let (ollama, requests) = serve_chat().await;
+
+    let mut coordinator =
+        Coordinator::new_with_tools(ollama, "qwen3".to_string(), vec![], Multiply);
+
+    let events: Vec<CoordinatorEvent> = coordinator
+        .chat_stream(vec![ChatMessage::user("What is 6 * 7?".to_string())])
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 6);
+    assert!(matches!(&events[0], CoordinatorEvent::Content(c) if c == "Let me compute it. "));
+    assert!(
+        matches!(&events[1], CoordinatorEvent::ToolCallStarted(call) if call.function.name() == "multiply")
+    );
+    match &events[2] {
+        CoordinatorEvent::ToolResult { call, result } => {
+            assert_eq!(call.function.arguments()["a"], 6);
+            assert_eq!(result, "\"42\"");
+        }
+        event => panic!("expected a tool result, got {:?}", event),
+    }
+    assert!(matches!(&events[3], CoordinatorEvent::Content(c) if c == "It is "));
+    assert!(matches!(&events[4], CoordinatorEvent::Content(c) if c == "42."));
+    match &events[5] {
+        CoordinatorEvent::Done {
+            message,
+            final_data,
+        } => {
+            assert_eq!(message.content, "It is 42.");
+            assert_eq!(final_data.as_ref().unwrap().eval_count, 3);
+        }
+        event => panic!("expected the final response, got {:?}", event),
+    }
+
+    // The model was queried again with the whole conversation and the result of the tool
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    assert!(requests.iter().all(|r| r["stream"] == true));
+    assert_eq!(requests[0]["tools"][0]["function"]["name"], "multiply");
+    let roles: Vec<&str> = requests[1]["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["user", "assistant", "tool"]);
+    assert_eq!(requests[1]["messages"][2]["content"], "\"42\"");
+}
+
+#[tokio::test]
+async fn test_coordinator_chat_stream_history() {
+    let (ollama, requests) = serve_chat().await;
+
+    let mut coordinator =
+        Coordinator::new_with_tools(ollama, "qwen3".to_string(), vec![], Multiply);
+
+    for question in ["What is 6 * 7?", "Are you sure?"] {
+        let mut events = coordinator.chat_stream(vec![ChatMessage::user(question.to_string())]);
+        while let Some(event) = events.next().await {
+            event.unwrap();
+        }
+    }
+
+    // The rounds of the first question are kept in the history
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 3);
+    let messages = requests[2]["messages"].as_array().unwrap();
+    let roles: Vec<&str> = messages
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["user", "assistant", "tool", "assistant", "user"]);
+    assert_eq!(messages[1]["content"], "Let me compute it. ");
+    assert_eq!(messages[1]["tool_calls"][0]["function"]["name"], "multiply");
+    assert_eq!(messages[3]["content"], "It is 42.");
+    assert_eq!(messages[4]["content"], "Are you sure?");
+}
@@ -0,0 +1,430 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ollama_rs::{
+    coordinator::{Coordinator, CoordinatorEvent, ToolErrorPolicy, ToolPolicy},
+    error::{OllamaError, ToolCallError},
+    generation::{chat::ChatMessage, tools::Tool},
+    Ollama,
+};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio_stream::StreamExt;
+
+use common::Response;
+
+const CALL_MULTIPLY: &str = r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"multiply","arguments":{"a":6,"b":7}}}]},"done":true}"#;
+const CALL_TWICE: &str = r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"multiply","arguments":{"a":6,"b":7}}},{"function":{"name":"multiply","arguments":{"a":2,"b":3}}}]},"done":true}"#;
+const CALL_UNKNOWN: &str = r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"divide","arguments":{"a":6,"b":7}}}]},"done":true}"#;
+const CALL_INVALID: &str = r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"multiply","arguments":{"a":"six"}}}]},"done":true}"#;
+const CALL_MULTIPLY_AND_UNKNOWN: &str = r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"multiply","arguments":{"a":6,"b":7}}},{"function":{"name":"divide","arguments":{"a":6,"b":7}}}]},"done":true}"#;
+const CALL_SHORT_SLEEP: &str = r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"sleep","arguments":{"millis":50}}}]},"done":true}"#;
+const CALL_SLEEP: &str = r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"sleep","arguments":{"millis":1000}}}]},"done":true}"#;
+const ANSWER: &str = r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"It is 42."},"done":true}"#;
+
+#[derive(Deserialize, JsonSchema)]
+struct MultiplyParams {
+    a: i64,
+    b: i64,
+}
+
+struct Multiply;
+
+impl Tool for Multiply {
+    type Params = MultiplyParams;
+
+    fn name() -> &'static str {
+        "multiply"
+    }
+
+    fn description() -> &'static str {
+        "Multiplies two numbers."
+    }
+
+    async fn call(
+        &mut self,
+        parameters: Self::Params,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        Ok((parameters.a * parameters.b).to_string())
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct SleepParams {
+    millis: u64,
+}
+
+struct Sleep;
+
+impl Tool for Sleep {
+    type Params = SleepParams;
+
+    fn name() -> &'static str {
+        "sleep"
+    }
+
+    fn description() -> &'static str {
+        "Waits for a while."
+    }
+
+    async fn call(
+        &mut self,
+        parameters: Self::Params,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+        tokio::time::sleep(Duration::from_millis(parameters.millis)).await;
+        Ok("done".to_string())
+    }
+}
+
+/// Starts a server answering each chat request with the response chosen by `respond`.
+/// Returns the bodies of the requests.
+async fn serve_chat<F>(respond: F) -> (Ollama, Arc<Mutex<Vec<Value>>>)
+where
+    F: Fn(&Value) -> &'static str + Send + Sync + 'static,
+{
+    let requests = Arc::new(Mutex::new(vec![]));
+
+    let bodies = requests.clone();
+    let port = common::serve(move |request| {
+        let body = request.json();
+        let res = format!("{}\n", respond(&body));
+        bodies.lock().unwrap().push(body);
+        Response::ok(res)
+    })
+    .await;
+
+    (Ollama::new("http://127.0.0.1", port), requests)
+}
+
+/// A model calling `multiply` as long as it is offered tools.
+fn looping(body: &Value) -> &'static str {
+    if body["tools"].is_null() {
+        ANSWER
+    } else {
+        CALL_MULTIPLY
+    }
+}
+
+/// A model making `call` until it receives the result of a tool.
+fn until_result(call: &'static str) -> impl Fn(&Value) -> &'static str {
+    move |body| {
+        let messages = body["messages"].as_array().unwrap();
+        if messages.iter().any(|m| m["role"] == "tool") {
+            ANSWER
+        } else {
+            call
+        }
+    }
+}
+
+fn coordinator(
+    ollama: Ollama,
+    policy: ToolPolicy,
+) -> Coordinator<Vec<ChatMessage>, (Multiply, Sleep)> {
+    Coordinator::new_with_tools(ollama, "qwen3", vec![], (Multiply, Sleep)).tool_policy(policy)
+}
+
+fn question() -> Vec<ChatMessage> {
+    vec![ChatMessage::user("What is 6 * 7?".to_string())]
+}
+
+/// The roles of the messages of a request.
+fn roles(request: &Value) -> Vec<&str> {
+    request["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect()
+}
+
+/// The contents of the tool messages of a request.
+fn tool_results(request: &Value) -> Vec<String> {
+    request["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|m| m["role"] == "tool")
+        .map(|m| m["content"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_max_rounds_forces_final_answer() {
+    let (ollama, requests) = serve_chat(looping).await;
+
+    let mut coordinator = coordinator(ollama, ToolPolicy::new().max_rounds(2));
+    let resp = coordinator.chat(question()).await.unwrap();
+    assert_eq!(resp.message.content, "It is 42.");
+
+    let bodies = requests.lock().unwrap().clone();
+    assert_eq!(bodies.len(), 3);
+    assert!(bodies[..2].iter().all(|r| r["tools"].is_array()));
+
+    // The last request has no tools, and asks the model to answer
+    assert!(bodies[2]["tools"].is_null());
+    let messages = bodies[2]["messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 6);
+    assert_eq!(messages.last().unwrap()["role"], "system");
+    assert_eq!(tool_results(&bodies[2]).len(), 2);
+
+    // The instruction is not kept in the history
+    coordinator
+        .chat(vec![ChatMessage::user("Thanks".to_string())])
+        .await
+        .unwrap();
+    let requests = requests.lock().unwrap().clone();
+    let roles: Vec<&str> = requests[3]["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        roles,
+        [
+            "user",
+            "assistant",
+            "tool",
+            "assistant",
+            "tool",
+            "assistant",
+            "user"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_max_rounds_without_final_answer() {
+    let (ollama, requests) = serve_chat(looping).await;
+
+    let policy = ToolPolicy::new().max_rounds(2).force_final_answer(false);
+    let mut coordinator = coordinator(ollama, policy);
+    let res = coordinator.chat(question()).await;
+    assert!(matches!(res, Err(OllamaError::ToolRoundLimitExceeded(2))));
+
+    // Tools were offered until the end, but only two rounds were run
+    let bodies = requests.lock().unwrap().clone();
+    assert_eq!(bodies.len(), 3);
+    assert!(bodies.iter().all(|r| r["tools"].is_array()));
+
+    // The calls that were not run are not left in the history
+    let _ = coordinator
+        .chat(vec![ChatMessage::user("Thanks".to_string())])
+        .await;
+    let requests = requests.lock().unwrap().clone();
+    let messages = requests[3]["messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 7);
+    assert_eq!(messages[5]["role"], "assistant");
+    assert_eq!(messages[5]["tool_calls"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_final_answer_calling_tools() {
+    // A model calling tools even when it is not offered any
+    let (ollama, requests) = serve_chat(|_| CALL_MULTIPLY).await;
+
+    let mut coordinator = coordinator(ollama, ToolPolicy::new().max_rounds(1));
+    let resp = coordinator.chat(question()).await.unwrap();
+    assert!(resp.message.tool_calls.is_empty());
+
+    coordinator
+        .chat(vec![ChatMessage::user("Thanks".to_string())])
+        .await
+        .unwrap();
+    let requests = requests.lock().unwrap().clone();
+    let messages = requests[2]["messages"].as_array().unwrap();
+    let roles: Vec<&str> = messages
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["user", "assistant", "tool", "assistant", "user"]);
+    assert_eq!(messages[3]["tool_calls"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_max_calls_per_round() {
+    let (ollama, requests) = serve_chat(until_result(CALL_TWICE)).await;
+
+    let policy = ToolPolicy::new().max_calls_per_round(1);
+    coordinator(ollama, policy).chat(question()).await.unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    let results = tool_results(&requests[1]);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0], "\"42\"");
+    assert!(results[1].contains("multiply was not called"));
+}
+
+#[tokio::test]
+async fn test_unknown_tool() {
+    let (ollama, _) = serve_chat(until_result(CALL_UNKNOWN)).await;
+
+    let res = coordinator(ollama, ToolPolicy::new())
+        .chat(question())
+        .await;
+    assert!(matches!(
+        res,
+        Err(OllamaError::ToolCallError(ToolCallError::UnknownToolName))
+    ));
+
+    let (ollama, requests) = serve_chat(until_result(CALL_UNKNOWN)).await;
+
+    let policy = ToolPolicy::new().on_error(ToolErrorPolicy::ReportToModel);
+    let resp = coordinator(ollama, policy).chat(question()).await.unwrap();
+    assert_eq!(resp.message.content, "It is 42.");
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(
+        tool_results(&requests[1]),
+        ["Error: there is no tool named divide"]
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_arguments() {
+    let (ollama, _) = serve_chat(until_result(CALL_INVALID)).await;
+
+    let res = coordinator(ollama, ToolPolicy::new())
+        .chat(question())
+        .await;
+    assert!(matches!(
+        res,
+        Err(OllamaError::ToolCallError(
+            ToolCallError::InvalidToolArguments(_)
+        ))
+    ));
+
+    let (ollama, requests) = serve_chat(until_result(CALL_INVALID)).await;
+
+    let policy = ToolPolicy::new().on_error(ToolErrorPolicy::ReportToModel);
+    coordinator(ollama, policy).chat(question()).await.unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    let results = tool_results(&requests[1]);
+    assert!(results[0].starts_with("Error: invalid arguments for multiply"));
+}
+
+#[tokio::test]
+async fn test_tool_timeout() {
+    let (ollama, _) = serve_chat(until_result(CALL_SLEEP)).await;
+
+    let policy = ToolPolicy::new().timeout(Duration::from_millis(10));
+    let res = coordinator(ollama, policy).chat(question()).await;
+    assert!(matches!(
+        res,
+        Err(OllamaError::ToolCallError(ToolCallError::Timeout(_)))
+    ));
+
+    let (ollama, requests) = serve_chat(until_result(CALL_SLEEP)).await;
+
+    let policy = ToolPolicy::new()
+        .timeout(Duration::from_millis(10))
+        .on_error(ToolErrorPolicy::ReportToModel);
+    coordinator(ollama, policy).chat(question()).await.unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(
+        tool_results(&requests[1]),
+        ["Error: sleep did not return within 10ms"]
+    );
+}
+
+#[tokio::test]
+async fn test_tool_timeout_per_tool() {
+    let (ollama, _) = serve_chat(until_result(CALL_SLEEP)).await;
+
+    let policy = ToolPolicy::new().tool_timeout("sleep", Duration::from_millis(10));
+    let res = coordinator(ollama, policy).chat(question()).await;
+    assert!(matches!(
+        res,
+        Err(OllamaError::ToolCallError(ToolCallError::Timeout(_)))
+    ));
+
+    let (ollama, requests) = serve_chat(until_result(CALL_SHORT_SLEEP)).await;
+
+    // The timeout of the tool overrides the one of every tool
+    let policy = ToolPolicy::new()
+        .timeout(Duration::from_millis(10))
+        .tool_timeout("sleep", Duration::from_secs(1));
+    coordinator(ollama, policy).chat(question()).await.unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(tool_results(&requests[1]), ["\"done\""]);
+}
+
+#[tokio::test]
+async fn test_failed_round_left_out_of_history() {
+    let (ollama, requests) = serve_chat(until_result(CALL_MULTIPLY_AND_UNKNOWN)).await;
+
+    let mut coordinator = coordinator(ollama, ToolPolicy::new());
+    let res = coordinator.chat(question()).await;
+    assert!(matches!(
+        res,
+        Err(OllamaError::ToolCallError(ToolCallError::UnknownToolName))
+    ));
+
+    // Neither the tool calls nor the result of the first one are sent with the next question
+    let _ = coordinator
+        .chat(vec![ChatMessage::user("Are you sure?".to_string())])
+        .await;
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(roles(&requests[1]), ["user", "user"]);
+}
+
+#[tokio::test]
+async fn test_failed_stream_round_left_out_of_history() {
+    let (ollama, requests) = serve_chat(until_result(CALL_MULTIPLY_AND_UNKNOWN)).await;
+
+    let mut coordinator = coordinator(ollama, ToolPolicy::new());
+    let events: Vec<_> = coordinator.chat_stream(question()).collect().await;
+    assert!(matches!(
+        events.last(),
+        Some(Err(OllamaError::ToolCallError(
+            ToolCallError::UnknownToolName
+        )))
+    ));
+    // The result of the first call was still streamed
+    assert!(events.iter().any(
+        |e| matches!(e, Ok(CoordinatorEvent::ToolResult { result, .. }) if result == "\"42\"")
+    ));
+
+    let _: Vec<_> = coordinator
+        .chat_stream(vec![ChatMessage::user("Are you sure?".to_string())])
+        .collect()
+        .await;
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(roles(&requests[1]), ["user", "user"]);
+}
+
+#[tokio::test]
+async fn test_chat_stream_max_rounds() {
+    let (ollama, requests) = serve_chat(looping).await;
+
+    let mut coordinator = coordinator(ollama, ToolPolicy::new().max_rounds(1));
+    let events: Vec<CoordinatorEvent> = coordinator
+        .chat_stream(question())
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+
+    let calls = events
+        .iter()
+        .filter(|e| matches!(e, CoordinatorEvent::ToolCallStarted(_)))
+        .count();
+    assert_eq!(calls, 1);
+    assert!(
+        matches!(events.last(), Some(CoordinatorEvent::Done { message, .. }) if message.content == "It is 42.")
+    );
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1]["tools"].is_null());
+}
@@ -1,16 +1,22 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
 use ollama_rs::{models::create::CreateModelRequest, Ollama};
 use tokio_stream::StreamExt;
 
+use common::Response;
+
 #[tokio::test]
 /// This test needs a Modelfile at /tmp to work
 async fn test_create_model_stream() {
     let ollama = Ollama::default();
 
-    let request = CreateModelRequest::new("testmodel".into())
+    let request = CreateModelRequest::new("testmodel")
         .license("Test".into())
         .system("You're a chat bot. (very useful information)".into())
         .template("Template".into())
-        .from_model("llama2:latest".into());
+        .from_model("llama2:latest");
 
     let mut res = ollama.create_model_stream(request).await.unwrap();
 
@@ -35,13 +41,82 @@ async fn test_create_model_stream() {
 async fn test_create_model() {
     let ollama = Ollama::default();
 
-    let request = CreateModelRequest::new("testmodel".into())
+    let request = CreateModelRequest::new("testmodel")
         .license("Test".into())
         .system("You're a chat bot. (very useful information)".into())
         .template("Template".into())
-        .from_model("llama2:latest".into());
+        .from_model("llama2:latest");
 
     let res = ollama.create_model(request).await.unwrap();
 
     assert!(res.message.eq("success"));
 }
+
+/// Starts a server without any blob, recording the request line and body of each request.
+async fn serve_create() -> (Ollama, Arc<Mutex<Vec<(String, String)>>>) {
+    let requests = Arc::new(Mutex::new(vec![]));
+
+    let recorded = requests.clone();
+    let port = common::serve(move |request| {
+        let res = if request.method() == "HEAD" {
+            Response::status("404 Not Found")
+        } else if request.line.starts_with("POST /api/create") {
+            Response::ok("{\"status\":\"success\"}\n")
+        } else {
+            Response::status("201 Created")
+        };
+        recorded.lock().unwrap().push((request.line, request.body));
+        res
+    })
+    .await;
+
+    (Ollama::new("http://127.0.0.1", port), requests)
+}
+
+#[tokio::test]
+async fn test_create_model_from_local_files() {
+    let dir = std::env::temp_dir().join("ollama-rs-test-create");
+    let weights = dir.join("weights");
+    tokio::fs::create_dir_all(&weights).await.unwrap();
+    tokio::fs::write(weights.join("model.safetensors"), b"weights")
+        .await
+        .unwrap();
+    tokio::fs::write(weights.join("config.json"), b"{}")
+        .await
+        .unwrap();
+    tokio::fs::write(weights.join("README.md"), b"# Model")
+        .await
+        .unwrap();
+    tokio::fs::write(dir.join("adapter.gguf"), b"adapter")
+        .await
+        .unwrap();
+
+    let (ollama, requests) = serve_create().await;
+    let request = CreateModelRequest::new("testmodel")
+        .local_weights(&weights)
+        .local_adapter(dir.join("adapter.gguf"));
+
+    let res = ollama.create_model(request).await.unwrap();
+    assert_eq!(res.message, "success");
+
+    let requests = requests.lock().unwrap().clone();
+    // An existence check and an upload for each of the three files, then the creation
+    assert_eq!(requests.len(), 7);
+
+    let (request_line, body) = requests.last().unwrap();
+    assert!(request_line.starts_with("POST /api/create"));
+    let body: serde_json::Value = serde_json::from_str(body).unwrap();
+    let files = body["files"].as_object().unwrap();
+    assert_eq!(files.len(), 2);
+    assert!(files["model.safetensors"]
+        .as_str()
+        .unwrap()
+        .starts_with("sha256:"));
+    assert!(files.contains_key("config.json"));
+    assert!(body["adapters"]
+        .as_object()
+        .unwrap()
+        .contains_key("adapter.gguf"));
+
+    tokio::fs::remove_dir_all(&dir).await.unwrap();
+}
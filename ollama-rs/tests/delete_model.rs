@@ -5,5 +5,5 @@ use ollama_rs::Ollama;
 async fn test_delete_model() {
     let ollama = Ollama::default();
 
-    ollama.delete_model("mario_copy".into()).await.unwrap();
+    ollama.delete_model("mario_copy").await.unwrap();
 }
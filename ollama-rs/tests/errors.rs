@@ -0,0 +1,81 @@
+use ollama_rs::{
+    error::{ApiError, ApiErrorKind, OllamaError},
+    generation::completion::request::GenerationRequest,
+    Ollama,
+};
+use reqwest::StatusCode;
+
+#[tokio::test]
+async fn test_model_not_found_error() {
+    let ollama = Ollama::default();
+
+    let res = ollama
+        .generate(GenerationRequest::new(
+            "this-model-does-not-exist:latest".to_string(),
+            "Hello",
+        ))
+        .await;
+
+    match res {
+        Err(OllamaError::ApiError(e)) => {
+            dbg!(&e);
+            assert_eq!(e.status, StatusCode::NOT_FOUND);
+            assert_eq!(e.kind, ApiErrorKind::ModelNotFound);
+        }
+        other => panic!("Expected an API error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_api_error_classification() {
+    let cases = [
+        (
+            StatusCode::NOT_FOUND,
+            "model \"llama9\" not found, try pulling it first",
+            ApiErrorKind::ModelNotFound,
+        ),
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "pull model manifest: file does not exist",
+            ApiErrorKind::ModelNotFound,
+        ),
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "model requires more system memory (12.3 GiB) than is available (4.0 GiB)",
+            ApiErrorKind::OutOfMemory,
+        ),
+        (
+            StatusCode::BAD_REQUEST,
+            "the input length exceeds the context length",
+            ApiErrorKind::ContextTooLong,
+        ),
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "server busy, please try again.  maximum pending requests exceeded",
+            ApiErrorKind::ServerOverloaded,
+        ),
+        (
+            StatusCode::UNAUTHORIZED,
+            "unauthorized",
+            ApiErrorKind::Unauthorized,
+        ),
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "llama runner process has terminated",
+            ApiErrorKind::ServerError,
+        ),
+        (
+            StatusCode::BAD_REQUEST,
+            "invalid options",
+            ApiErrorKind::BadRequest,
+        ),
+    ];
+
+    for (status, message, kind) in cases {
+        let err = ApiError::new(status, message);
+        assert_eq!(err.kind, kind, "{}", message);
+    }
+
+    assert!(ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "server busy").is_retryable());
+    assert!(!ApiError::new(StatusCode::NOT_FOUND, "model not found").is_retryable());
+}
@@ -25,12 +25,10 @@ async fn test_generation_stream() {
     let mut done = false;
     while let Some(res) = res.next().await {
         let res = res.unwrap();
-        for ele in res {
-            dbg!(&ele);
-            if ele.done {
-                done = true;
-                break;
-            }
+        dbg!(&res);
+        if res.done {
+            done = true;
+            break;
         }
     }
 
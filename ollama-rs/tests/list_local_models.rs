@@ -6,3 +6,30 @@ async fn test_list_local_models() {
 
     dbg!(models);
 }
+
+#[test]
+fn test_local_model_deserialization() {
+    let model: ollama_rs::models::LocalModel = serde_json::from_str(
+        r#"{
+            "name": "llama3.2:latest",
+            "model": "llama3.2:latest",
+            "modified_at": "2025-01-01T00:00:00Z",
+            "size": 2019393189,
+            "digest": "a80c4f17acd55265feec403c7aef86be0c25983ab279d83f3bcd3abbcb5b8b72",
+            "details": {
+                "parent_model": "",
+                "format": "gguf",
+                "family": "llama",
+                "families": ["llama"],
+                "parameter_size": "3.2B",
+                "quantization_level": "Q4_K_M"
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(model.model, "llama3.2:latest");
+    assert_eq!(model.details.format, "gguf");
+    assert_eq!(model.details.families, Some(vec!["llama".to_string()]));
+    assert_eq!(model.details.parameter_size, "3.2B");
+}
@@ -0,0 +1,8 @@
+#[tokio::test]
+async fn test_list_running_models() {
+    let ollama = ollama_rs::Ollama::default();
+
+    let models = ollama.list_running_models().await.unwrap();
+
+    dbg!(models);
+}
@@ -0,0 +1,19 @@
+use ollama_rs::{generation::parameters::KeepAlive, Ollama};
+
+#[tokio::test]
+async fn test_load_and_unload_model() {
+    let ollama = Ollama::default();
+
+    ollama
+        .load_model("llama2:latest".to_string(), KeepAlive::Indefinitely)
+        .await
+        .unwrap();
+
+    let running = ollama.list_running_models().await.unwrap();
+    assert!(running.iter().any(|m| m.name == "llama2:latest"));
+
+    ollama.unload_model("llama2".to_string()).await.unwrap();
+
+    let running = ollama.list_running_models().await.unwrap();
+    assert!(!running.iter().any(|m| m.name == "llama2:latest"));
+}
@@ -0,0 +1,133 @@
+use ollama_rs::{
+    error::OllamaError,
+    generation::{
+        chat::{
+            request::ChatMessageRequest, ChatMessage, ChatMessageAccumulator, ChatMessageResponse,
+        },
+        completion::{request::GenerationRequest, GenerationResponse},
+        logprobs::MAX_TOP_LOGPROBS,
+        parameters::{KeepAlive, TimeUnit},
+    },
+    Ollama,
+};
+
+fn chat_response(token: &str, logprob: f64, done: bool) -> ChatMessageResponse {
+    serde_json::from_value(serde_json::json!({
+        "model": "llama3.2",
+        "created_at": "2025-01-01T00:00:00Z",
+        "message": {"role": "assistant", "content": token},
+        "done": done,
+        "logprobs": [{
+            "token": token,
+            "logprob": logprob,
+            "bytes": token.as_bytes(),
+            "top_logprobs": [
+                {"token": token, "logprob": logprob, "bytes": token.as_bytes()},
+                {"token": "other", "logprob": -3.5, "bytes": b"other"}
+            ]
+        }]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_serialize_requests() {
+    let request = serde_json::to_value(
+        GenerationRequest::new("llama3.2", "<|begin_of_text|>Hi")
+            .raw(true)
+            .top_logprobs(5),
+    )
+    .unwrap();
+    assert_eq!(request["raw"], true);
+    assert_eq!(request["logprobs"], true);
+    assert_eq!(request["top_logprobs"], 5);
+
+    let request = serde_json::to_value(
+        ChatMessageRequest::new("llama3.2", vec![ChatMessage::user("Hi".to_string())])
+            .logprobs(true)
+            .keep_alive(KeepAlive::Until {
+                time: 10,
+                unit: TimeUnit::Minutes,
+            }),
+    )
+    .unwrap();
+    assert_eq!(request["logprobs"], true);
+    assert_eq!(request["keep_alive"], "10m");
+    assert!(request.get("top_logprobs").is_none());
+
+    let request = serde_json::to_value(GenerationRequest::new("llama3.2", "Hi")).unwrap();
+    assert!(request.get("raw").is_none());
+    assert!(request.get("logprobs").is_none());
+}
+
+#[test]
+fn test_generation_logprobs() {
+    let response: GenerationResponse = serde_json::from_str(
+        r#"{
+            "model": "llama3.2",
+            "created_at": "2025-01-01T00:00:00Z",
+            "response": "Yes",
+            "done": true,
+            "logprobs": [{"token": "Yes", "logprob": -0.10536051565782628}]
+        }"#,
+    )
+    .unwrap();
+
+    let logprobs = response.logprobs.unwrap();
+    assert_eq!(logprobs.len(), 1);
+    assert_eq!(logprobs[0].token, "Yes");
+    assert!((logprobs[0].probability() - 0.9).abs() < 1e-9);
+    assert!(logprobs[0].top_logprobs.is_empty());
+
+    let response: GenerationResponse = serde_json::from_str(
+        r#"{"model": "llama3.2", "created_at": "2025-01-01T00:00:00Z", "response": "Yes", "done": false}"#,
+    )
+    .unwrap();
+    assert!(response.logprobs.is_none());
+}
+
+#[test]
+fn test_accumulate_chat_logprobs() {
+    let mut accumulator = ChatMessageAccumulator::new();
+    accumulator.push(&chat_response("Hello", -0.01, false));
+    accumulator.push(&chat_response("!", -0.2, true));
+
+    assert_eq!(accumulator.content(), "Hello!");
+
+    let logprobs = accumulator.logprobs();
+    assert_eq!(logprobs.len(), 2);
+    assert_eq!(logprobs[0].token, "Hello");
+    assert_eq!(logprobs[0].bytes.as_deref(), Some(&b"Hello"[..]));
+    assert_eq!(logprobs[1].logprob, -0.2);
+    assert_eq!(logprobs[1].top_logprobs[1].token, "other");
+}
+
+#[tokio::test]
+async fn test_reject_too_many_top_logprobs() {
+    // Nothing listens there, as nothing is sent
+    let ollama = Ollama::new("http://127.0.0.1", 1);
+
+    let res = ollama
+        .generate(GenerationRequest::new("llama3.2", "Hi").top_logprobs(21))
+        .await;
+    match res {
+        Err(e @ OllamaError::InvalidModelOption { .. }) => {
+            assert_eq!(
+                e.to_string(),
+                "Invalid model option top_logprobs: must be at most 20"
+            );
+        }
+        res => panic!("expected an invalid option error, got {:?}", res),
+    }
+
+    let request = ChatMessageRequest::new("llama3.2", vec![ChatMessage::user("Hi".to_string())])
+        .top_logprobs(MAX_TOP_LOGPROBS + 1);
+    let res = ollama.send_chat_messages(request).await;
+    assert!(matches!(
+        res,
+        Err(OllamaError::InvalidModelOption {
+            option: "top_logprobs",
+            ..
+        })
+    ));
+}
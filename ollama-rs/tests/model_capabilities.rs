@@ -0,0 +1,127 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use ollama_rs::{
+    error::OllamaError,
+    generation::{
+        chat::{request::ChatMessageRequest, ChatMessage},
+        completion::request::GenerationRequest,
+        embeddings::request::GenerateEmbeddingsRequest,
+        images::Image,
+    },
+    models::ModelCapability,
+    Ollama,
+};
+
+use common::Response;
+
+/// Starts a server where every model only supports completion, recording the request lines.
+async fn serve_text_model() -> (Ollama, Arc<Mutex<Vec<String>>>) {
+    let requests = Arc::new(Mutex::new(vec![]));
+
+    let recorded = requests.clone();
+    let port = common::serve(move |request| {
+        let body = if request.line.starts_with("POST /api/show") {
+            r#"{"modelfile":"","capabilities":["completion"]}"#
+        } else {
+            r#"{"model":"llama2","created_at":"2024-01-01T00:00:00Z","response":"Hi","done":true}"#
+        };
+        recorded.lock().unwrap().push(request.line);
+        Response::ok(body)
+    })
+    .await;
+
+    let mut ollama = Ollama::new("http://127.0.0.1", port);
+    ollama.set_validate_model_capabilities(true);
+    (ollama, requests)
+}
+
+#[tokio::test]
+async fn test_reject_unsupported_requests() {
+    let (ollama, requests) = serve_text_model().await;
+
+    let res = ollama
+        .generate(GenerationRequest::new("llama2", "Hi").add_image(Image::from_base64("aGk=")))
+        .await;
+    assert!(matches!(
+        res,
+        Err(OllamaError::UnsupportedModelCapability {
+            capability: ModelCapability::Vision,
+            ..
+        })
+    ));
+
+    let res = ollama
+        .generate(GenerationRequest::new("llama2", "fn main() {").suffix("}"))
+        .await;
+    assert!(matches!(
+        res,
+        Err(OllamaError::UnsupportedModelCapability {
+            capability: ModelCapability::Insert,
+            ..
+        })
+    ));
+
+    let message = ChatMessage::user("Hi".to_string()).add_image(Image::from_base64("aGk="));
+    let res = ollama
+        .send_chat_messages(ChatMessageRequest::new("llama2", vec![message]))
+        .await;
+    assert!(matches!(
+        res,
+        Err(OllamaError::UnsupportedModelCapability {
+            capability: ModelCapability::Vision,
+            ..
+        })
+    ));
+
+    let res = ollama
+        .generate_embeddings(GenerateEmbeddingsRequest::new("llama2", "Hi".into()))
+        .await;
+    match res {
+        Err(e @ OllamaError::UnsupportedModelCapability { .. }) => {
+            assert_eq!(e.to_string(), "The model llama2 does not support embedding");
+        }
+        _ => panic!("expected an unsupported capability error"),
+    }
+
+    // The capabilities were requested once, and nothing else was sent
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_accept_supported_requests() {
+    let (ollama, requests) = serve_text_model().await;
+
+    let res = ollama
+        .generate(GenerationRequest::new("llama2", "Hi"))
+        .await
+        .unwrap();
+    assert_eq!(res.response, "Hi");
+    // Another name of the same model uses the cached capabilities
+    ollama
+        .generate(GenerationRequest::new("llama2:latest", "Hi"))
+        .await
+        .unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].starts_with("POST /api/show"));
+    assert!(requests[1].starts_with("POST /api/generate"));
+    assert!(requests[2].starts_with("POST /api/generate"));
+}
+
+#[tokio::test]
+async fn test_no_validation_by_default() {
+    let (mut ollama, requests) = serve_text_model().await;
+    ollama.set_validate_model_capabilities(false);
+
+    ollama
+        .generate(GenerationRequest::new("llama2", "Hi").add_image(Image::from_base64("aGk=")))
+        .await
+        .unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("POST /api/generate"));
+}
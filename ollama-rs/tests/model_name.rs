@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+
+use ollama_rs::{
+    error::OllamaError,
+    generation::chat::{request::ChatMessageRequest, ChatMessage},
+    models::ModelName,
+};
+
+#[test]
+fn test_parse_defaults() {
+    let name = ModelName::parse("llama3").unwrap();
+
+    assert_eq!(name.registry(), "registry.ollama.ai");
+    assert_eq!(name.namespace(), "library");
+    assert_eq!(name.model(), "llama3");
+    assert_eq!(name.tag(), "latest");
+    assert_eq!(name.digest(), None);
+}
+
+#[test]
+fn test_parse_full() {
+    let name = ModelName::parse("localhost:5000/me/my-model:7b-q4_0@sha256-6a0746a1ec1a").unwrap();
+
+    assert_eq!(name.registry(), "localhost:5000");
+    assert_eq!(name.namespace(), "me");
+    assert_eq!(name.model(), "my-model");
+    assert_eq!(name.tag(), "7b-q4_0");
+    assert_eq!(name.digest(), Some("sha256:6a0746a1ec1a"));
+    assert_eq!(
+        name.to_string(),
+        "localhost:5000/me/my-model:7b-q4_0@sha256:6a0746a1ec1a"
+    );
+
+    // A port is not a tag
+    let name = ModelName::parse("localhost:5000/me/my-model").unwrap();
+    assert_eq!(name.tag(), "latest");
+
+    let name = ModelName::parse("me/my-model:v1").unwrap();
+    assert_eq!(name.to_string(), "me/my-model:v1");
+}
+
+#[test]
+fn test_normalized_comparison() {
+    let names = [
+        "llama3",
+        "llama3:latest",
+        "LLaMA3:Latest",
+        "library/llama3",
+        "registry.ollama.ai/library/llama3:latest",
+    ];
+
+    let set = names
+        .iter()
+        .map(|n| ModelName::parse(n).unwrap())
+        .collect::<HashSet<_>>();
+    assert_eq!(set.len(), 1);
+
+    let name = ModelName::parse("llama3").unwrap();
+    assert!(name.matches("llama3:latest"));
+    assert!(!name.matches("llama3:8b"));
+    assert!(!name.matches("me/llama3"));
+}
+
+#[test]
+fn test_invalid_names() {
+    for name in [
+        "",
+        ":latest",
+        "-model",
+        "my model",
+        "llama3:",
+        "llama3:la/test",
+        "a/b/c/d",
+        "llama3@md5:abc",
+        "llama3@sha256:xyz",
+    ] {
+        assert!(
+            matches!(
+                ModelName::parse(name),
+                Err(OllamaError::InvalidModelName(_))
+            ),
+            "{:?} should be invalid",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_accepted_as_model_name() {
+    let name = ModelName::parse("llama3").unwrap();
+
+    let request = ChatMessageRequest::new(name, vec![ChatMessage::user("Hi".to_string())]);
+
+    assert_eq!(request.model_name, "llama3:latest");
+}
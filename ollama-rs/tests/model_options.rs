@@ -0,0 +1,164 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use ollama_rs::{
+    error::OllamaError, generation::completion::request::GenerationRequest, models::ModelOptions,
+    Ollama,
+};
+
+use common::Response;
+
+/// Starts a server answering every generation request, recording the request bodies.
+async fn serve_generate(defaults: ModelOptions) -> (Ollama, Arc<Mutex<Vec<String>>>) {
+    let bodies = Arc::new(Mutex::new(vec![]));
+
+    let recorded = bodies.clone();
+    let port = common::serve(move |request| {
+        recorded.lock().unwrap().push(request.body);
+        Response::ok(
+            r#"{"model":"llama2","created_at":"2024-01-01T00:00:00Z","response":"Hi","done":true}"#,
+        )
+    })
+    .await;
+
+    let ollama = Ollama::builder()
+        .port(port)
+        .default_options(defaults)
+        .build()
+        .unwrap();
+    (ollama, bodies)
+}
+
+#[test]
+fn test_serialize_options() {
+    let options = ModelOptions::default()
+        .num_keep(8)
+        .min_p(0.05)
+        .presence_penalty(0.5)
+        .frequency_penalty(0.25)
+        .penalize_newline(false)
+        .num_batch(256)
+        .use_mmap(false)
+        .low_vram(true);
+
+    let json = serde_json::to_value(&options).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "num_keep": 8,
+            "min_p": 0.05f32,
+            "presence_penalty": 0.5,
+            "frequency_penalty": 0.25,
+            "penalize_newline": false,
+            "num_batch": 256,
+            "use_mmap": false,
+            "low_vram": true
+        })
+    );
+}
+
+#[test]
+fn test_merge_options() {
+    let defaults = ModelOptions::default()
+        .temperature(0.2)
+        .num_ctx(4096)
+        .stop(vec!["</s>".to_string()]);
+    let request = ModelOptions::default().temperature(0.9).seed(42);
+
+    let options = defaults.merge(request);
+
+    assert_eq!(options.get_temperature(), Some(0.9));
+    assert_eq!(options.get_seed(), Some(42));
+    assert_eq!(options.get_num_ctx(), Some(4096));
+    assert_eq!(options.get_stop(), Some(&["</s>".to_string()][..]));
+    assert_eq!(options.get_top_k(), None);
+}
+
+#[test]
+fn test_validate_options() {
+    assert!(ModelOptions::default().validate().is_ok());
+    assert!(ModelOptions::default()
+        .temperature(0.0)
+        .top_p(1.0)
+        .num_predict(-2)
+        .repeat_last_n(-1)
+        .validate()
+        .is_ok());
+
+    let invalid = [
+        ModelOptions::default().top_p(1.5),
+        ModelOptions::default().min_p(-0.1),
+        ModelOptions::default().temperature(-1.0),
+        ModelOptions::default().temperature(f32::NAN),
+        ModelOptions::default().mirostat(3),
+        ModelOptions::default().num_ctx(0),
+        ModelOptions::default().num_predict(-3),
+        ModelOptions::default().frequency_penalty(f32::INFINITY),
+    ];
+    for options in invalid {
+        assert!(
+            matches!(
+                options.validate(),
+                Err(OllamaError::InvalidModelOption { .. })
+            ),
+            "{:?} should be invalid",
+            options
+        );
+    }
+
+    let error = ModelOptions::default().top_p(2.0).validate().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid model option top_p: must be between 0 and 1"
+    );
+
+    let error = ModelOptions::default()
+        .temperature(-0.5)
+        .validate()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid model option temperature: must be 0 or greater"
+    );
+}
+
+#[tokio::test]
+async fn test_request_options_layered_over_defaults() {
+    let (ollama, bodies) =
+        serve_generate(ModelOptions::default().num_ctx(4096).temperature(0.2)).await;
+
+    ollama
+        .generate(
+            GenerationRequest::new("llama2", "Hi")
+                .options(ModelOptions::default().temperature(0.9)),
+        )
+        .await
+        .unwrap();
+
+    let bodies = bodies.lock().unwrap().clone();
+    let body: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+    assert_eq!(body["options"]["num_ctx"], 4096);
+    assert_eq!(body["options"]["temperature"], 0.9);
+}
+
+#[tokio::test]
+async fn test_invalid_options_not_sent() {
+    let (ollama, bodies) = serve_generate(ModelOptions::default()).await;
+
+    let res = ollama
+        .generate(
+            GenerationRequest::new("llama2", "Hi")
+                .options(ModelOptions::default().top_k(40).top_p(3.0)),
+        )
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(OllamaError::InvalidModelOption {
+            option: "top_p",
+            ..
+        })
+    ));
+    assert!(bodies.lock().unwrap().is_empty());
+}
@@ -0,0 +1,181 @@
+use ollama_rs::{
+    error::OllamaError,
+    generation::chat::{ChatMessage, MessageRole},
+    models::{create::CreateModelRequest, parameters::OptionChange, ModelInfo, ModelOptions},
+};
+
+const PARAMETERS: &str = r#"num_ctx                        8192
+stop                           "<|start_header_id|>"
+stop                           "<|eot_id|>"
+temperature                    0.6
+top_p                          0.9
+use_mmap                       false
+some_future_option             1"#;
+
+#[test]
+fn test_options_from_parameters() {
+    let model_info: ModelInfo = serde_json::from_value(serde_json::json!({
+        "modelfile": "FROM llama3\n",
+        "parameters": PARAMETERS,
+    }))
+    .unwrap();
+
+    let options = model_info.options().unwrap();
+
+    assert_eq!(options.get_num_ctx(), Some(8192));
+    assert_eq!(options.get_temperature(), Some(0.6));
+    assert_eq!(options.get_top_p(), Some(0.9));
+    assert_eq!(options.get_use_mmap(), Some(false));
+    assert_eq!(
+        options.get_stop(),
+        Some(&["<|start_header_id|>".to_string(), "<|eot_id|>".to_string()][..])
+    );
+    assert_eq!(options.get_top_k(), None);
+}
+
+#[test]
+fn test_invalid_parameter_value() {
+    let res = ModelOptions::from_parameters("num_ctx lots");
+
+    assert!(matches!(
+        res,
+        Err(OllamaError::InvalidModelOption {
+            option: "num_ctx",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_options_roundtrip_through_modelfile() {
+    let options = ModelOptions::default()
+        .temperature(0.2)
+        .num_predict(-1)
+        .penalize_newline(true)
+        .stop(vec!["</s>".to_string(), "say \"bye\"".to_string()]);
+
+    let modelfile = options.to_modelfile();
+    assert_eq!(
+        modelfile,
+        concat!(
+            "PARAMETER temperature 0.2\n",
+            "PARAMETER num_predict -1\n",
+            "PARAMETER penalize_newline true\n",
+            "PARAMETER stop \"</s>\"\n",
+            "PARAMETER stop \"say \\\"bye\\\"\"\n",
+        )
+    );
+
+    let parsed = ModelOptions::from_modelfile(&format!(
+        "FROM llama3.2\nTEMPLATE \"\"\"\nPARAMETER top_k 1\n\"\"\"\n{}",
+        modelfile
+    ))
+    .unwrap();
+    assert_eq!(parsed.to_parameters(), options.to_parameters());
+    assert_eq!(parsed.get_top_k(), None);
+}
+
+#[test]
+fn test_escaped_stop_sequences_roundtrip() {
+    let options = ModelOptions::default().stop(vec![
+        "\n\nUser:".to_string(),
+        "\tend\r".to_string(),
+        "back\\slash \u{1b}".to_string(),
+    ]);
+
+    let modelfile = options.to_modelfile();
+    assert_eq!(
+        modelfile,
+        concat!(
+            "PARAMETER stop \"\\n\\nUser:\"\n",
+            "PARAMETER stop \"\\tend\\r\"\n",
+            "PARAMETER stop \"back\\\\slash \\x1b\"\n",
+        )
+    );
+    assert_eq!(modelfile.lines().count(), 3);
+
+    let parsed = ModelOptions::from_modelfile(&modelfile).unwrap();
+    assert_eq!(parsed.get_stop(), options.get_stop());
+    assert!(options.diff(&parsed).is_empty());
+}
+
+#[test]
+fn test_go_quoted_parameters() {
+    // As printed by Ollama in `ModelInfo::parameters`
+    let options = ModelOptions::from_parameters(concat!(
+        r#"stop                           "\n\nUser:""#,
+        "\n",
+        r#"stop                           "caf\u00e9 \"\U0001F642\"""#,
+        "\n",
+        r#"stop                           "\q""#,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        options.get_stop(),
+        Some(
+            &[
+                "\n\nUser:".to_string(),
+                "café \"🙂\"".to_string(),
+                "\\q".to_string()
+            ][..]
+        )
+    );
+}
+
+#[test]
+fn test_diff_options() {
+    let baked_in: ModelOptions = PARAMETERS.parse().unwrap();
+    let overrides = ModelOptions::default()
+        .temperature(0.6)
+        .num_ctx(4096)
+        .seed(42)
+        .stop(vec!["<|eot_id|>".to_string()]);
+
+    let changes = baked_in.diff(&overrides);
+
+    assert_eq!(
+        changes,
+        vec![
+            OptionChange {
+                option: "num_ctx",
+                from: vec!["8192".to_string()],
+                to: vec!["4096".to_string()],
+            },
+            OptionChange {
+                option: "seed",
+                from: vec![],
+                to: vec!["42".to_string()],
+            },
+            OptionChange {
+                option: "stop",
+                from: vec!["<|start_header_id|>".to_string(), "<|eot_id|>".to_string()],
+                to: vec!["<|eot_id|>".to_string()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_create_request_to_modelfile() {
+    let request = CreateModelRequest::new("mario")
+        .from_model("llama3.2")
+        .system("You are Mario from Super Mario Bros.".to_string())
+        .template("{{ .System }}\n{{ .Prompt }}".to_string())
+        .parameters(ModelOptions::default().temperature(1.0))
+        .messages(vec![ChatMessage::new(
+            MessageRole::User,
+            "Who are you?".to_string(),
+        )]);
+
+    assert_eq!(
+        request.to_modelfile(),
+        concat!(
+            "FROM llama3.2\n",
+            "TEMPLATE \"\"\"{{ .System }}\n{{ .Prompt }}\"\"\"\n",
+            "SYSTEM You are Mario from Super Mario Bros.\n",
+            "PARAMETER temperature 1\n",
+            "MESSAGE user Who are you?\n",
+        )
+    );
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ollama_rs::{
+    error::OllamaError,
+    generation::{
+        chat::{request::ChatMessageRequest, ChatMessage},
+        completion::request::GenerationRequest,
+    },
+    Ollama,
+};
+use tokio_stream::StreamExt;
+
+use common::Response;
+
+/// Starts a server answering every request with `frames`, each one sent as a separate HTTP chunk.
+async fn serve_chunks(frames: Vec<Vec<u8>>) -> Ollama {
+    let port =
+        common::serve(move |_| Response::chunked(frames.clone(), Duration::from_millis(20))).await;
+    Ollama::new("http://127.0.0.1", port)
+}
+
+fn response(text: &str, done: bool) -> String {
+    format!(
+        r#"{{"model":"llama2:latest","created_at":"2023-08-04T08:52:19.385406455-07:00","response":"{}","done":{}}}"#,
+        text, done
+    )
+}
+
+#[tokio::test]
+async fn test_split_frames() {
+    let body = format!(
+        "{}\n{}\n\n{}",
+        response("Ça ", false),
+        response("va 🙂", false),
+        response("", true)
+    )
+    .into_bytes();
+
+    // Split in the middle of objects and in the middle of multi-byte characters
+    let first = body.iter().position(|&b| b == 0xC3).unwrap() + 1;
+    let second = body.iter().position(|&b| b == 0xF0).unwrap() + 2;
+    let frames = vec![
+        body[..first].to_vec(),
+        body[first..second].to_vec(),
+        body[second..].to_vec(),
+    ];
+
+    let ollama = serve_chunks(frames).await;
+    let responses = ollama
+        .generate_stream(GenerationRequest::new("llama2:latest".to_string(), "Hi"))
+        .await
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let text = responses
+        .iter()
+        .map(|r| r.response.as_str())
+        .collect::<String>();
+    assert_eq!(responses.len(), 3);
+    assert_eq!(text, "Ça va 🙂");
+    assert!(responses[2].done);
+}
+
+#[tokio::test]
+async fn test_error_in_stream() {
+    let frames = vec![
+        br#"{"status":"pulling manifest"}"#.to_vec(),
+        b"\n{\"error\":\"pull model manifest: file does not exist\"}\n".to_vec(),
+    ];
+
+    let ollama = serve_chunks(frames).await;
+    let mut stream = ollama
+        .pull_model_stream("nonexistent".to_string(), false)
+        .await
+        .unwrap();
+
+    let status = stream.next().await.unwrap().unwrap();
+    assert_eq!(status.message, "pulling manifest");
+
+    match stream.next().await.unwrap() {
+        Err(OllamaError::InternalError(e)) => {
+            assert_eq!(e.message, "pull model manifest: file does not exist")
+        }
+        res => panic!("Expected an internal error, got {:?}", res),
+    }
+
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_invalid_line_in_chat_history_stream() {
+    let frames = vec![
+        br#"{"model":"llama2:latest","created_at":"2023-08-04T08:52:19.385406455-07:00","message":{"role":"assistant","content":"The"},"done":false}"#.to_vec(),
+        b"\n{\"model\":\n".to_vec(),
+    ];
+
+    let ollama = serve_chunks(frames).await;
+    let history = Arc::new(Mutex::new(vec![]));
+    let mut stream = ollama
+        .send_chat_messages_with_history_stream(
+            history.clone(),
+            ChatMessageRequest::new(
+                "llama2:latest".to_string(),
+                vec![ChatMessage::user("Why is the sky blue?".to_string())],
+            ),
+        )
+        .await
+        .unwrap();
+
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.message.content, "The");
+    assert!(matches!(
+        stream.next().await.unwrap(),
+        Err(OllamaError::JsonError(_))
+    ));
+    assert!(stream.next().await.is_none());
+
+    // The partial answer is not added to the history
+    assert_eq!(history.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,94 @@
+use ollama_rs::models::{
+    create::CreateModelStatus,
+    progress::{track_progress, ModelPhase, ProgressTracker},
+    pull::PullModelStatus,
+};
+use tokio_stream::StreamExt;
+
+fn pull_statuses() -> Vec<PullModelStatus> {
+    [
+        r#"{"status":"pulling manifest"}"#,
+        r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a0746a1ec1a","total":300}"#,
+        r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a0746a1ec1a","total":300,"completed":150}"#,
+        r#"{"status":"pulling 4fa551d4f938","digest":"sha256:4fa551d4f938","total":100,"completed":100}"#,
+        r#"{"status":"pulling 6a0746a1ec1a","digest":"sha256:6a0746a1ec1a","total":300,"completed":300}"#,
+        r#"{"status":"verifying sha256 digest"}"#,
+        r#"{"status":"writing manifest"}"#,
+        r#"{"status":"success"}"#,
+    ]
+    .iter()
+    .map(|s| serde_json::from_str(s).unwrap())
+    .collect()
+}
+
+#[test]
+fn test_track_pull_progress() {
+    let mut tracker = ProgressTracker::new();
+    let progress = pull_statuses()
+        .iter()
+        .map(|s| tracker.update(s))
+        .collect::<Vec<_>>();
+
+    assert_eq!(progress[0].phase, ModelPhase::PullingManifest);
+    assert_eq!(progress[0].percentage(), None);
+
+    assert_eq!(
+        progress[2].phase,
+        ModelPhase::Downloading {
+            digest: "sha256:6a0746a1ec1a".to_string()
+        }
+    );
+    assert_eq!(progress[2].percentage(), Some(50.0));
+
+    // A second layer is added to the total
+    assert_eq!(progress[3].layers.len(), 2);
+    assert_eq!(progress[3].total, 400);
+    assert_eq!(progress[3].completed, 250);
+
+    assert_eq!(progress[4].percentage(), Some(100.0));
+    assert_eq!(progress[5].phase, ModelPhase::VerifyingDigest);
+    assert_eq!(progress[6].phase, ModelPhase::WritingManifest);
+    assert!(progress[7].is_success());
+}
+
+#[test]
+fn test_create_phases() {
+    let mut tracker = ProgressTracker::new();
+    let phase = |message: &str| {
+        let status: CreateModelStatus =
+            serde_json::from_value(serde_json::json!({ "status": message })).unwrap();
+        ModelPhase::from_status(&status)
+    };
+
+    assert_eq!(
+        phase("using existing layer sha256:6a07"),
+        ModelPhase::Creating
+    );
+    assert_eq!(
+        phase("quantizing F16 model to Q4_K_M"),
+        ModelPhase::Creating
+    );
+    assert_eq!(
+        phase("something new"),
+        ModelPhase::Other("something new".to_string())
+    );
+
+    let status: CreateModelStatus = serde_json::from_str(r#"{"status":"success"}"#).unwrap();
+    assert!(tracker.update(&status).is_success());
+}
+
+#[tokio::test]
+async fn test_track_progress_stream() {
+    let stream = tokio_stream::iter(pull_statuses().into_iter().map(Ok));
+
+    let progress = track_progress(stream)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(progress.len(), 8);
+    assert!(progress.last().unwrap().is_success());
+    assert_eq!(progress.last().unwrap().completed, 400);
+}
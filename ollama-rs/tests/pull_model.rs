@@ -6,7 +6,7 @@ async fn test_pull_model() {
     let ollama = Ollama::default();
 
     let mut res = ollama
-        .pull_model_stream("llama2:latest".into(), false)
+        .pull_model_stream("llama2:latest", false)
         .await
         .unwrap();
 
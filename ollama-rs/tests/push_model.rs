@@ -7,7 +7,7 @@ async fn test_push_model() {
     let ollama = Ollama::default();
 
     let mut res = ollama
-        .push_model_stream("test_model:latest".into(), false)
+        .push_model_stream("test_model:latest", false)
         .await
         .unwrap();
 
@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+
+use ollama_rs::{
+    error::{ApiError, ApiErrorKind, OllamaError},
+    retry::RetryPolicy,
+    Ollama,
+};
+use reqwest::StatusCode;
+
+#[tokio::test]
+async fn test_retry_connection_refused() {
+    // Nothing should be listening on this port
+    let mut ollama = Ollama::new("http://127.0.0.1", 1);
+    ollama.set_retry_policy(
+        RetryPolicy::default()
+            .max_attempts(3)
+            .initial_backoff(Duration::from_millis(100))
+            .jitter(false),
+    );
+
+    let start = Instant::now();
+    let res = ollama.list_local_models().await;
+
+    assert!(matches!(res, Err(OllamaError::ReqwestError(ref e)) if e.is_connect()));
+    // Two retries: 100ms then 200ms
+    assert!(start.elapsed() >= Duration::from_millis(300));
+}
+
+#[test]
+fn test_retry_policy_delays() {
+    let policy = RetryPolicy::default()
+        .max_attempts(4)
+        .initial_backoff(Duration::from_secs(1))
+        .max_backoff(Duration::from_secs(3))
+        .jitter(false);
+
+    let overloaded: OllamaError =
+        ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "server busy").into();
+
+    assert_eq!(
+        policy.next_delay(1, &overloaded, false),
+        Some(Duration::from_secs(1))
+    );
+    assert_eq!(
+        policy.next_delay(2, &overloaded, false),
+        Some(Duration::from_secs(2))
+    );
+    assert_eq!(
+        policy.next_delay(3, &overloaded, false),
+        Some(Duration::from_secs(3))
+    );
+    assert_eq!(policy.next_delay(4, &overloaded, false), None);
+
+    let server_error: OllamaError =
+        ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "runner crashed").into();
+    assert!(policy.next_delay(1, &server_error, true).is_some());
+    assert!(policy.next_delay(1, &server_error, false).is_none());
+
+    let not_found: OllamaError = ApiError::new(StatusCode::NOT_FOUND, "model not found").into();
+    assert!(policy.next_delay(1, &not_found, true).is_none());
+
+    let policy = policy.retryable_kinds(vec![ApiErrorKind::ModelNotFound]);
+    assert!(policy.next_delay(1, &not_found, true).is_some());
+}
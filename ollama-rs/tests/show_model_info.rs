@@ -1,3 +1,11 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use ollama_rs::models::{show_info::ShowModelInfoRequest, ModelCapability};
+
+use common::Response;
+
 #[cfg(feature = "modelfile")]
 use modelfile::modelfile::{Instruction, Parameter};
 
@@ -34,3 +42,125 @@ async fn test_model_info_modelfile_param_stop() {
 
     assert_eq!(stop, "[INST]");
 }
+
+#[test]
+fn test_model_info_deserialization() {
+    let model_info: ollama_rs::models::ModelInfo = serde_json::from_str(
+        r#"{
+            "modelfile": "FROM llama3.2\n",
+            "parameters": "stop \"<|eot_id|>\"",
+            "template": "{{ .Prompt }}",
+            "system": "You are a helpful assistant.",
+            "details": {
+                "parent_model": "",
+                "format": "gguf",
+                "family": "llama",
+                "families": ["llama", "clip"],
+                "parameter_size": "3.2B",
+                "quantization_level": "Q4_K_M"
+            },
+            "model_info": {
+                "general.architecture": "llama",
+                "general.parameter_count": 3212749888,
+                "llama.context_length": 131072,
+                "llama.embedding_length": 3072,
+                "llama.block_count": 28,
+                "llama.attention.head_count": 24
+            },
+            "projector_info": {"clip.has_vision_encoder": true},
+            "capabilities": ["completion", "tools", "vision", "audio"],
+            "modified_at": "2025-01-01T00:00:00Z"
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(model_info.details.family, "llama");
+    assert_eq!(model_info.details.quantization_level, "Q4_K_M");
+    assert_eq!(model_info.system, "You are a helpful assistant.");
+    assert_eq!(model_info.modified_at, "2025-01-01T00:00:00Z");
+    assert_eq!(model_info.projector_info.len(), 1);
+
+    assert!(model_info.has_capability(&ModelCapability::Tools));
+    assert!(model_info.has_capability(&ModelCapability::Vision));
+    assert!(!model_info.has_capability(&ModelCapability::Embedding));
+    assert_eq!(
+        model_info.capabilities[3],
+        ModelCapability::Other("audio".to_string())
+    );
+
+    assert_eq!(model_info.architecture(), Some("llama"));
+    assert_eq!(model_info.parameter_count(), Some(3212749888));
+    assert_eq!(model_info.context_length(), Some(131072));
+    assert_eq!(model_info.embedding_length(), Some(3072));
+    assert_eq!(model_info.block_count(), Some(28));
+    assert_eq!(model_info.head_count(), Some(24));
+}
+
+#[test]
+fn test_model_info_deserialization_old_server() {
+    let model_info: ollama_rs::models::ModelInfo =
+        serde_json::from_str(r#"{"modelfile": "FROM llama2\n", "template": "{{ .Prompt }}"}"#)
+            .unwrap();
+
+    assert!(model_info.capabilities.is_empty());
+    assert_eq!(model_info.architecture(), None);
+    assert_eq!(model_info.context_length(), None);
+}
+
+const VERBOSE_INFO: &str = r#"{
+    "modelfile": "FROM llama3.2\n",
+    "template": "{{ .Prompt }}",
+    "model_info": {
+        "general.architecture": "llama",
+        "llama.vocab_size": 4,
+        "tokenizer.ggml.model": "gpt2",
+        "tokenizer.ggml.tokens": ["<s>", "</s>", "a", "b"],
+        "tokenizer.chat_template": "{% for message in messages %}{{ message.content }}{% endfor %}"
+    },
+    "tensors": [
+        {"name": "token_embd.weight", "type": "Q6_K", "shape": [3072, 128256]},
+        {"name": "blk.0.attn_norm.weight", "type": "F32", "shape": [3072]}
+    ]
+}"#;
+
+/// Starts a server answering every request with `VERBOSE_INFO`, and recording the request bodies.
+async fn serve_show() -> (ollama_rs::Ollama, Arc<Mutex<Vec<String>>>) {
+    let bodies = Arc::new(Mutex::new(vec![]));
+
+    let recorded = bodies.clone();
+    let port = common::serve(move |request| {
+        recorded.lock().unwrap().push(request.body);
+        Response::ok(VERBOSE_INFO)
+    })
+    .await;
+
+    (ollama_rs::Ollama::new("http://127.0.0.1", port), bodies)
+}
+
+#[tokio::test]
+async fn test_show_model_info_verbose() {
+    let (ollama, bodies) = serve_show().await;
+
+    let model_info = ollama
+        .show_model_info(ShowModelInfoRequest::new("llama3.2").verbose(true))
+        .await
+        .unwrap();
+    ollama.show_model_info("llama3.2").await.unwrap();
+
+    let bodies = bodies.lock().unwrap().clone();
+    let verbose: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+    let not_verbose: serde_json::Value = serde_json::from_str(&bodies[1]).unwrap();
+    assert_eq!(verbose["name"], "llama3.2");
+    assert_eq!(verbose["verbose"], true);
+    assert_eq!(not_verbose["verbose"], false);
+
+    assert_eq!(model_info.tensors.len(), 2);
+    assert_eq!(model_info.tensors[0].name, "token_embd.weight");
+    assert_eq!(model_info.tensors[0].tensor_type, "Q6_K");
+    assert_eq!(model_info.tensors[0].shape, vec![3072, 128256]);
+
+    assert_eq!(model_info.tokenizer_model(), Some("gpt2"));
+    assert_eq!(model_info.tokens(), Some(vec!["<s>", "</s>", "a", "b"]));
+    assert_eq!(model_info.vocab_size(), Some(4));
+    assert!(model_info.chat_template().unwrap().starts_with("{% for"));
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use ollama_rs::{
+    generation::chat::{
+        events::{chat_stream_events, ChatStreamEvent},
+        request::ChatMessageRequest,
+        ChatMessage, MessageRole,
+    },
+    Ollama,
+};
+use tokio_stream::StreamExt;
+
+use common::Response;
+
+const TOOL_CALL_STREAM: &str = concat!(
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","thinking":"I need the weather."},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"Let me check. "},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"get_weather","arguments":{"city":"Paris"}}}]},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"get_time","arguments":{"timezone":"CET"}}}]},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":""},"done":true,"total_duration":1,"prompt_eval_count":1,"prompt_eval_duration":1,"eval_count":1,"eval_duration":1}"#,
+    "\n",
+);
+
+/// Starts a server answering every request with `TOOL_CALL_STREAM`.
+async fn serve_tool_calls() -> Ollama {
+    let port = common::serve(|_| Response::ok(TOOL_CALL_STREAM)).await;
+    Ollama::new("http://127.0.0.1", port)
+}
+
+fn request() -> ChatMessageRequest {
+    ChatMessageRequest::new(
+        "qwen3",
+        vec![ChatMessage::user("Weather in Paris?".to_string())],
+    )
+}
+
+#[tokio::test]
+async fn test_chat_stream_events() {
+    let ollama = serve_tool_calls().await;
+
+    let stream = ollama.send_chat_messages_stream(request()).await.unwrap();
+    let events: Vec<ChatStreamEvent> = chat_stream_events(stream)
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 5);
+    assert!(matches!(&events[0], ChatStreamEvent::Thinking(t) if t == "I need the weather."));
+    assert!(matches!(&events[1], ChatStreamEvent::Content(c) if c == "Let me check. "));
+    match &events[2] {
+        ChatStreamEvent::ToolCall(call) => {
+            assert_eq!(call.function.name(), "get_weather");
+            assert_eq!(call.function.arguments()["city"], "Paris");
+        }
+        event => panic!("expected a tool call, got {:?}", event),
+    }
+    assert!(
+        matches!(&events[3], ChatStreamEvent::ToolCall(call) if call.function.name() == "get_time")
+    );
+    match &events[4] {
+        ChatStreamEvent::Done {
+            message,
+            final_data,
+        } => {
+            assert_eq!(message.content, "Let me check. ");
+            assert_eq!(message.thinking.as_deref(), Some("I need the weather."));
+            assert_eq!(message.tool_calls.len(), 2);
+            assert!(final_data.is_some());
+        }
+        event => panic!("expected the end of the response, got {:?}", event),
+    }
+}
+
+#[tokio::test]
+async fn test_history_stream_records_tool_calls() {
+    let ollama = serve_tool_calls().await;
+
+    let history = Arc::new(Mutex::new(vec![]));
+    let mut stream = ollama
+        .send_chat_messages_with_history_stream(history.clone(), request())
+        .await
+        .unwrap();
+    while let Some(res) = stream.next().await {
+        res.unwrap();
+    }
+
+    let history = history.lock().unwrap().clone();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].role, MessageRole::Assistant);
+    assert_eq!(history[1].content, "Let me check. ");
+
+    let names: Vec<&str> = history[1]
+        .tool_calls
+        .iter()
+        .map(|c| c.function.name())
+        .collect();
+    assert_eq!(names, ["get_weather", "get_time"]);
+}
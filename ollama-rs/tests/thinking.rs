@@ -0,0 +1,197 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use ollama_rs::{
+    generation::{
+        chat::{request::ChatMessageRequest, ChatMessage, ChatMessageAccumulator},
+        completion::{
+            request::GenerationRequest, GenerationResponse, GenerationResponseAccumulator,
+        },
+        parameters::Think,
+    },
+    Ollama,
+};
+use tokio_stream::StreamExt;
+
+use common::Response;
+
+const THINKING_STREAM: &str = concat!(
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","thinking":"The user "},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"","thinking":"says hi."},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"Hello"},"done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"!"},"done":true,"total_duration":1,"prompt_eval_count":1,"prompt_eval_duration":1,"eval_count":1,"eval_duration":1}"#,
+    "\n",
+);
+
+const GENERATE_THINKING_STREAM: &str = concat!(
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","response":"","thinking":"The user ","done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","response":"","thinking":"says hi.","done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","response":"Hello","done":false}"#,
+    "\n",
+    r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","response":"!","done":true,"context":[1,2,3]}"#,
+    "\n",
+);
+
+const THINKING_RESPONSE: &str = r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":"Hello!","thinking":"The user says hi."},"done":true,"total_duration":1,"prompt_eval_count":1,"prompt_eval_duration":1,"eval_count":1,"eval_duration":1}"#;
+
+/// Starts a server answering streamed chat requests with `THINKING_STREAM` and the others with
+/// `THINKING_RESPONSE`, recording the request bodies.
+async fn serve_thinking() -> (Ollama, Arc<Mutex<Vec<serde_json::Value>>>) {
+    let bodies = Arc::new(Mutex::new(vec![]));
+
+    let recorded = bodies.clone();
+    let port = common::serve(move |request| {
+        let body = request.json();
+        let res = if body["stream"] == true {
+            THINKING_STREAM
+        } else {
+            THINKING_RESPONSE
+        };
+        recorded.lock().unwrap().push(body);
+        Response::ok(res)
+    })
+    .await;
+
+    (Ollama::new("http://127.0.0.1", port), bodies)
+}
+
+fn request() -> ChatMessageRequest {
+    ChatMessageRequest::new("qwen3", vec![ChatMessage::user("Hi".to_string())]).think(true)
+}
+
+#[test]
+fn test_serialize_think() {
+    let json = |think: Think| serde_json::to_value(think).unwrap();
+
+    assert_eq!(json(Think::Enabled), serde_json::json!(true));
+    assert_eq!(json(false.into()), serde_json::json!(false));
+    assert_eq!(json(Think::High), serde_json::json!("high"));
+    assert_eq!(
+        serde_json::from_str::<Think>(r#""medium""#).unwrap(),
+        Think::Medium
+    );
+    assert!(serde_json::from_str::<Think>(r#""max""#).is_err());
+
+    let request =
+        serde_json::to_value(GenerationRequest::new("gpt-oss", "Hi").think(Think::Low)).unwrap();
+    assert_eq!(request["think"], "low");
+    let request = serde_json::to_value(GenerationRequest::new("gpt-oss", "Hi")).unwrap();
+    assert!(request.get("think").is_none());
+}
+
+#[test]
+fn test_generation_response_thinking() {
+    let response: GenerationResponse = serde_json::from_str(
+        r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","response":"","thinking":"Hmm","done":false}"#,
+    )
+    .unwrap();
+
+    assert_eq!(response.thinking.as_deref(), Some("Hmm"));
+
+    let response: GenerationResponse = serde_json::from_str(
+        r#"{"model":"qwen3","created_at":"2025-01-01T00:00:00Z","response":"Hi","done":false}"#,
+    )
+    .unwrap();
+    let json = serde_json::to_value(response).unwrap();
+    assert!(json.get("thinking").is_none());
+    assert!(json.get("logprobs").is_none());
+}
+
+#[tokio::test]
+async fn test_accumulate_generation_thinking() {
+    let port = common::serve(|_| Response::ok(GENERATE_THINKING_STREAM)).await;
+    let ollama = Ollama::new("http://127.0.0.1", port);
+
+    let mut stream = ollama
+        .generate_stream(GenerationRequest::new("qwen3", "Hi").think(true))
+        .await
+        .unwrap();
+    let mut accumulator = GenerationResponseAccumulator::new();
+    while let Some(res) = stream.next().await {
+        accumulator.push(&res.unwrap());
+    }
+
+    assert!(accumulator.is_done());
+    assert_eq!(accumulator.thinking(), "The user says hi.");
+    assert_eq!(accumulator.response(), "Hello!");
+    assert_eq!(accumulator.context().unwrap().0, [1, 2, 3]);
+    assert!(accumulator.logprobs().is_empty());
+}
+
+#[tokio::test]
+async fn test_accumulate_thinking() {
+    let (ollama, bodies) = serve_thinking().await;
+
+    let mut stream = ollama.send_chat_messages_stream(request()).await.unwrap();
+    let mut accumulator = ChatMessageAccumulator::new();
+    while let Some(res) = stream.next().await {
+        accumulator.push(&res.unwrap());
+    }
+
+    assert!(accumulator.is_done());
+    assert_eq!(accumulator.thinking(), "The user says hi.");
+    assert_eq!(accumulator.content(), "Hello!");
+    assert_eq!(
+        accumulator.message().thinking.as_deref(),
+        Some("The user says hi.")
+    );
+    assert_eq!(bodies.lock().unwrap()[0]["think"], true);
+}
+
+#[tokio::test]
+async fn test_thinking_left_out_of_history() {
+    let (mut ollama, _) = serve_thinking().await;
+
+    let history = Arc::new(Mutex::new(vec![]));
+    let mut stream = ollama
+        .send_chat_messages_with_history_stream(history.clone(), request())
+        .await
+        .unwrap();
+    while let Some(res) = stream.next().await {
+        res.unwrap();
+    }
+
+    let mut other_history = vec![];
+    let res = ollama
+        .send_chat_messages_with_history(&mut other_history, request())
+        .await
+        .unwrap();
+    assert_eq!(res.message.thinking.as_deref(), Some("The user says hi."));
+
+    for history in [history.lock().unwrap().clone(), other_history] {
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].content, "Hello!");
+        assert_eq!(history[1].thinking, None);
+    }
+}
+
+#[tokio::test]
+async fn test_thinking_kept_in_history() {
+    let (mut ollama, bodies) = serve_thinking().await;
+    ollama.set_keep_thinking_in_history(true);
+
+    let history = Arc::new(Mutex::new(vec![]));
+    for _ in 0..2 {
+        let mut stream = ollama
+            .send_chat_messages_with_history_stream(history.clone(), request())
+            .await
+            .unwrap();
+        while let Some(res) = stream.next().await {
+            res.unwrap();
+        }
+    }
+
+    let history = history.lock().unwrap().clone();
+    assert_eq!(history.len(), 4);
+    assert_eq!(history[1].thinking.as_deref(), Some("The user says hi."));
+
+    // The reasoning is sent back with the next messages
+    let bodies = bodies.lock().unwrap().clone();
+    assert_eq!(bodies[1]["messages"][1]["thinking"], "The user says hi.");
+}
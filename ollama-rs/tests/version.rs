@@ -0,0 +1,112 @@
+mod common;
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use ollama_rs::{
+    error::OllamaError,
+    generation::{
+        chat::{request::ChatMessageRequest, ChatMessage},
+        parameters::{FormatType, JsonStructure},
+    },
+    version::{Capability, OllamaVersion},
+    Ollama,
+};
+
+use common::Response;
+
+/// Starts a server answering every request with `{"version": <version>}`, counting the requests.
+async fn serve_version(version: &'static str) -> (Ollama, Arc<AtomicUsize>) {
+    let requests = Arc::new(AtomicUsize::new(0));
+
+    let count = requests.clone();
+    let port = common::serve(move |_| {
+        count.fetch_add(1, Ordering::SeqCst);
+        Response::ok(format!(r#"{{"version":"{}"}}"#, version))
+    })
+    .await;
+
+    (Ollama::new("http://127.0.0.1", port), requests)
+}
+
+#[tokio::test]
+async fn test_version() {
+    let ollama = Ollama::default();
+
+    let version = ollama.version().await.unwrap();
+
+    dbg!(version);
+}
+
+#[test]
+fn test_parse_version() {
+    let version: OllamaVersion = "0.5.7".parse().unwrap();
+    assert_eq!(version, OllamaVersion::new(0, 5, 7));
+    assert_eq!(version.to_string(), "0.5.7");
+
+    let version: OllamaVersion = "v0.6.0-rc1+abc".parse().unwrap();
+    assert_eq!(version.pre.as_deref(), Some("rc1"));
+    assert_eq!(version.to_string(), "0.6.0-rc1");
+
+    assert!("0.5".parse::<OllamaVersion>().is_err());
+    assert!("0.5.x".parse::<OllamaVersion>().is_err());
+    assert!("0.0.0".parse::<OllamaVersion>().unwrap().is_dev_build());
+}
+
+#[test]
+fn test_compare_versions() {
+    let v = |s: &str| s.parse::<OllamaVersion>().unwrap();
+
+    assert!(v("0.5.0") > v("0.4.7"));
+    assert!(v("0.10.0") > v("0.9.6"));
+    assert!(v("0.5.0-rc1") < v("0.5.0"));
+    assert!(v("0.5.0-rc1") > v("0.4.9"));
+    assert!(v("0.6.0-rc2") < v("0.6.0-rc10"));
+    assert!(v("0.6.0-rc") < v("0.6.0-rc1"));
+    assert!(v("0.6.0-alpha") < v("0.6.0-alpha.1"));
+    assert!(v("0.6.0-alpha.2") < v("0.6.0-alpha.10"));
+    assert!(v("0.6.0-alpha.1") < v("0.6.0-alpha.beta"));
+    assert!(v("0.6.0-beta") > v("0.6.0-alpha.1"));
+    assert!(v("0.5.0") >= Capability::StructuredOutputs.min_version());
+}
+
+#[tokio::test]
+async fn test_unsupported_capability() {
+    let (mut ollama, requests) = serve_version("0.4.7").await;
+    ollama.set_check_capabilities(true);
+
+    #[derive(schemars::JsonSchema)]
+    #[allow(dead_code)]
+    struct Output {
+        answer: String,
+    }
+
+    let request = ChatMessageRequest::new(
+        "llama3.2:latest".to_string(),
+        vec![ChatMessage::user("Why is the sky blue?".to_string())],
+    )
+    .format(FormatType::StructuredJson(JsonStructure::new::<Output>()));
+
+    for _ in 0..2 {
+        match ollama.send_chat_messages(request.clone()).await {
+            Err(OllamaError::UnsupportedCapability {
+                capability,
+                server_version,
+            }) => {
+                assert_eq!(capability, Capability::StructuredOutputs);
+                assert_eq!(server_version, OllamaVersion::new(0, 4, 7));
+            }
+            res => panic!("Expected an unsupported capability, got {:?}", res),
+        }
+    }
+
+    // The version is only requested once and the chat request is never sent
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert!(ollama.supports(Capability::Tools).await.unwrap());
+    assert!(!ollama
+        .supports(Capability::StructuredOutputs)
+        .await
+        .unwrap());
+}